@@ -0,0 +1,266 @@
+use std::{collections::HashSet, time::Duration};
+
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::{timeout_at, Instant},
+};
+
+use crate::{
+    client::WlClient,
+    error::WlError,
+    ids::TrafficInfoName,
+    models::{MessageCode, MonitorRequest, MonitorResponse, MonitorResponseData, MAX_STOP_IDS},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchConfig {
+    window: Duration,
+    max_stops: usize,
+}
+
+impl BatchConfig {
+    pub fn new() -> Self {
+        BatchConfig {
+            window: Duration::from_millis(20),
+            max_stops: MAX_STOP_IDS,
+        }
+    }
+
+    /// How long to wait for further requests after the first one of a batch arrived.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Maximum number of stop IDs of a batch, it is sent as soon as the next request
+    /// would not fit. Larger single requests are sent on their own.
+    pub fn max_stops(mut self, max_stops: usize) -> Self {
+        self.max_stops = max_stops.max(1);
+        self
+    }
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        BatchConfig::new()
+    }
+}
+
+type Reply = oneshot::Sender<Result<MonitorResponse, WlError>>;
+
+#[derive(Debug)]
+struct BatchItem {
+    request: MonitorRequest,
+    reply: Reply,
+}
+
+/// Merges concurrent `MonitorRequest`s into a single HTTP call.
+///
+/// Requests arriving within the batch window are combined into one request with the union
+/// of their stop IDs and traffic info flags. Each caller receives only the monitors of its
+/// own stops. Requests using `diva` or `a_area` cannot be split by stop and are sent on their own.
+///
+/// Must be created inside a tokio runtime. Once that runtime shuts down, requests fail with
+/// `WlError::Transport`. Cloning is cheap, all clones feed the same batches.
+#[derive(Debug, Clone)]
+pub struct MonitorBatcher {
+    sender: mpsc::UnboundedSender<BatchItem>,
+}
+
+impl MonitorBatcher {
+    pub fn new(client: WlClient, config: BatchConfig) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(collect_batches(client, config, receiver));
+        MonitorBatcher { sender }
+    }
+
+    pub async fn monitor(&self, request: &MonitorRequest) -> Result<MonitorResponse, WlError> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send(BatchItem {
+                request: request.clone(),
+                reply,
+            })
+            .map_err(|_| batch_task_stopped())?;
+        response.await.map_err(|_| batch_task_stopped())?
+    }
+}
+
+/// The task collecting batches ends with the runtime the batcher was created in.
+fn batch_task_stopped() -> WlError {
+    WlError::transport("batch task is not running, its runtime was shut down")
+}
+
+fn is_batchable(request: &MonitorRequest) -> bool {
+    request.diva.is_none() && !request.a_area && !request.stop_id.is_empty()
+}
+
+async fn collect_batches(
+    client: WlClient,
+    config: BatchConfig,
+    mut receiver: mpsc::UnboundedReceiver<BatchItem>,
+) {
+    // item that did not fit into the previous batch
+    let mut next = None;
+    loop {
+        let first = match next.take() {
+            Some(item) => item,
+            None => match receiver.recv().await {
+                Some(item) => item,
+                None => return,
+            },
+        };
+        if !is_batchable(&first.request) {
+            tokio::spawn(execute_single(client.clone(), first));
+            continue;
+        }
+
+        let deadline = Instant::now() + config.window;
+        let mut stops = first.request.stop_id.len();
+        let mut batch = vec![first];
+        while stops < config.max_stops {
+            match timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(item))
+                    if is_batchable(&item.request)
+                        && stops + item.request.stop_id.len() > config.max_stops =>
+                {
+                    next = Some(item);
+                    break;
+                }
+                Ok(Some(item)) if is_batchable(&item.request) => {
+                    stops += item.request.stop_id.len();
+                    batch.push(item);
+                }
+                Ok(Some(item)) => {
+                    tokio::spawn(execute_single(client.clone(), item));
+                }
+                Ok(None) | Err(_) => break,
+            }
+        }
+        tokio::spawn(execute_batch(client.clone(), batch));
+    }
+}
+
+async fn execute_single(client: WlClient, item: BatchItem) {
+    let result = client.monitor(&item.request).await;
+    let _ = item.reply.send(result);
+}
+
+async fn execute_batch(client: WlClient, mut batch: Vec<BatchItem>) {
+    if batch.len() == 1 {
+        execute_single(client, batch.remove(0)).await;
+        return;
+    }
+
+    let merged = merge_requests(batch.iter().map(|item| &item.request));
+    match client.monitor(&merged).await {
+        Ok(response) => {
+            for item in batch {
+                let response = split_response(&response, &merged, &item.request);
+                let _ = item.reply.send(Ok(response));
+            }
+        }
+        // a single unknown stop must not fail the whole batch
+        Err(WlError::Api {
+            code: MessageCode::StopDoesNotExist | MessageCode::NoDataFound,
+            ..
+        }) => {
+            for item in batch {
+                tokio::spawn(execute_single(client.clone(), item));
+            }
+        }
+        Err(err) => {
+            for item in batch {
+                let _ = item.reply.send(Err(err.clone()));
+            }
+        }
+    }
+}
+
+fn merge_requests<'a>(requests: impl Iterator<Item = &'a MonitorRequest>) -> MonitorRequest {
+    let mut merged = MonitorRequest::new();
+    for request in requests {
+        for stop_id in &request.stop_id {
+            if !merged.stop_id.contains(stop_id) {
+                merged.stop_id.push(*stop_id);
+            }
+        }
+        for traffic_info in &request.activate_traffic_info {
+            if !merged.activate_traffic_info.contains(traffic_info) {
+                merged.activate_traffic_info.push(traffic_info.clone());
+            }
+        }
+    }
+    merged
+}
+
+/// Extracts the part of a merged response that belongs to `request`.
+fn split_response(
+    response: &MonitorResponse,
+    merged: &MonitorRequest,
+    request: &MonitorRequest,
+) -> MonitorResponse {
+    let monitors: Vec<_> = response
+        .data
+        .monitors
+        .iter()
+        .filter(|monitor| {
+            request
+                .stop_id
+                .contains(&monitor.location_stop.properties.attributes.rbl)
+        })
+        .cloned()
+        .collect();
+
+    let referenced: HashSet<&TrafficInfoName> = monitors
+        .iter()
+        .filter_map(|monitor| monitor.ref_traffic_info_names.as_ref())
+        .flatten()
+        .collect();
+    // categories only present because another request of the batch activated them
+    let foreign_categories: HashSet<String> = merged
+        .activate_traffic_info
+        .iter()
+        .filter(|traffic_info| !request.activate_traffic_info.contains(traffic_info))
+        .map(|traffic_info| traffic_info.to_string())
+        .collect();
+    let foreign_category_ids: HashSet<i32> = response
+        .data
+        .traffic_info_categories
+        .iter()
+        .flatten()
+        .filter(|category| foreign_categories.contains(&category.name))
+        .map(|category| category.id)
+        .collect();
+    let traffic_info_categories =
+        response
+            .data
+            .traffic_info_categories
+            .as_ref()
+            .map(|categories| {
+                categories
+                    .iter()
+                    .filter(|category| !foreign_category_ids.contains(&category.id))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            });
+
+    let traffic_infos = response.data.traffic_infos.as_ref().map(|traffic_infos| {
+        traffic_infos
+            .iter()
+            .filter(|info| referenced.contains(&info.name))
+            .filter(|info| !foreign_category_ids.contains(&info.ref_traffic_info_category_id))
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+
+    MonitorResponse {
+        message: response.message.clone(),
+        data: MonitorResponseData {
+            monitors,
+            traffic_infos,
+            traffic_info_categories,
+            traffic_info_category_groups: response.data.traffic_info_category_groups.clone(),
+        },
+    }
+}
@@ -0,0 +1,337 @@
+//! Synchronous client for callers without an async runtime.
+//!
+//! Shares URL building and response parsing with the async `WlClient`. Must not be
+//! used from within an async runtime.
+
+use std::{
+    io::{self, Read},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
+
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+use crate::client::Certificate;
+use crate::{
+    client::{decode_response, ApiResponse, Proxy, DEFAULT_USER_AGENT, WL_ENDPOINT},
+    error::WlError,
+    models::{
+        BuildRequestUrl, MonitorRequest, MonitorResponse, TrafficInfoListRequest,
+        TrafficInfoListResponse,
+    },
+    retry::{parse_retry_after, RetryPolicy},
+};
+
+#[derive(Debug, Clone)]
+pub struct WlClient {
+    http: reqwest::blocking::Client,
+    timeout: Option<Duration>,
+    base_url: String,
+    check_message_code: bool,
+    retry_policy: RetryPolicy,
+}
+
+impl WlClient {
+    pub fn new() -> Self {
+        WlClient::builder()
+            .build()
+            .expect("default client configuration should always be valid")
+    }
+
+    pub fn builder() -> WlClientBuilder {
+        WlClientBuilder::new()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn monitor(&self, request: &MonitorRequest) -> Result<MonitorResponse, WlError> {
+        self.get(request)
+    }
+
+    pub fn traffic_info_list(
+        &self,
+        request: &TrafficInfoListRequest,
+    ) -> Result<TrafficInfoListResponse, WlError> {
+        self.get(request)
+    }
+
+    fn get<T: ApiResponse>(&self, request: &impl BuildRequestUrl) -> Result<T, WlError> {
+        let url = self.base_url.clone() + &request.build_request_url();
+        let mut attempt = 1;
+        loop {
+            let (result, retry_after) = self.attempt::<T>(&url);
+            match result {
+                Err(err)
+                    if attempt < self.retry_policy.max_attempts
+                        && self.retry_policy.is_retryable(&err) =>
+                {
+                    thread::sleep(self.retry_policy.delay(attempt, retry_after));
+                    attempt += 1;
+                }
+                result => return result.map(|(response, _)| response),
+            }
+        }
+    }
+
+    fn attempt<T: ApiResponse>(
+        &self,
+        url: &str,
+    ) -> (Result<(T, Bytes), WlError>, Option<Duration>) {
+        let started = Instant::now();
+        let mut response = match self.http.get(url).send() {
+            Ok(response) => response,
+            Err(err) => return (Err(err.into()), None),
+        };
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        let result = match self.read_body(&mut response, started) {
+            Ok(body) => decode_response(status.as_u16(), body, self.check_message_code),
+            Err(err) => Err(err),
+        };
+        (result, retry_after)
+    }
+
+    /// Reads the body piece by piece, so the timeout of `http` applies to each read, and
+    /// gives up once the overall `timeout` has passed since the request was sent.
+    fn read_body(
+        &self,
+        response: &mut reqwest::blocking::Response,
+        started: Instant,
+    ) -> Result<Bytes, WlError> {
+        let mut body = Vec::new();
+        let mut buffer = [0; 8192];
+        loop {
+            let read = response.read(&mut buffer).map_err(WlError::transport)?;
+            if read == 0 {
+                return Ok(Bytes::from(body));
+            }
+            body.extend_from_slice(&buffer[..read]);
+            if self
+                .timeout
+                .is_some_and(|timeout| started.elapsed() > timeout)
+            {
+                return Err(WlError::transport(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "request timed out",
+                )));
+            }
+        }
+    }
+}
+
+impl Default for WlClient {
+    fn default() -> Self {
+        WlClient::new()
+    }
+}
+
+struct DefaultClient {
+    builder: Option<WlClientBuilder>,
+    client: Option<WlClient>,
+}
+
+static DEFAULT_CLIENT: Mutex<DefaultClient> = Mutex::new(DefaultClient {
+    builder: None,
+    client: None,
+});
+
+/// Configures the client used by the `run_blocking()` convenience methods of the requests.
+pub fn set_default_client(builder: WlClientBuilder) {
+    let mut default = DEFAULT_CLIENT.lock().unwrap();
+    default.builder = Some(builder);
+    default.client = None;
+}
+
+/// Shared client used by the `run_blocking()` convenience methods of the requests.
+pub(crate) fn default_client() -> Result<WlClient, WlError> {
+    let mut default = DEFAULT_CLIENT.lock().unwrap();
+    if let Some(client) = &default.client {
+        return Ok(client.clone());
+    }
+    let client = match &default.builder {
+        Some(builder) => builder.clone().build()?,
+        None => WlClient::new(),
+    };
+    Ok(default.client.insert(client).clone())
+}
+
+#[derive(Debug, Clone)]
+pub struct WlClientBuilder {
+    base_url: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    user_agent: String,
+    default_headers: HeaderMap,
+    proxies: Vec<Proxy>,
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    root_certificates: Vec<Certificate>,
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    built_in_root_certificates: bool,
+    #[cfg(feature = "gzip")]
+    gzip: bool,
+    #[cfg(feature = "brotli")]
+    brotli: bool,
+    check_message_code: bool,
+    retry_policy: RetryPolicy,
+}
+
+impl WlClientBuilder {
+    pub fn new() -> Self {
+        WlClientBuilder {
+            base_url: String::from(WL_ENDPOINT),
+            timeout: None,
+            connect_timeout: None,
+            read_timeout: None,
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            default_headers: HeaderMap::new(),
+            proxies: vec![],
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            root_certificates: vec![],
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            built_in_root_certificates: true,
+            #[cfg(feature = "gzip")]
+            gzip: true,
+            #[cfg(feature = "brotli")]
+            brotli: true,
+            check_message_code: true,
+            retry_policy: RetryPolicy::none(),
+        }
+    }
+
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url: String = base_url.into();
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum time to wait for the response head and for each read of the body.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Sends requests through an HTTP(S) proxy. Can be called multiple times, the first
+    /// matching proxy is used. Without one the system proxy settings apply.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Trusts an additional root certificate, e.g. of a corporate TLS-intercepting proxy.
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Whether the built-in root certificates are trusted. Enabled by default.
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    pub fn built_in_root_certificates(mut self, enable: bool) -> Self {
+        self.built_in_root_certificates = enable;
+        self
+    }
+
+    /// Whether gzip compressed responses are requested and decompressed. Enabled by default.
+    #[cfg(feature = "gzip")]
+    pub fn gzip(mut self, enable: bool) -> Self {
+        self.gzip = enable;
+        self
+    }
+
+    /// Whether brotli compressed responses are requested and decompressed. Enabled by default.
+    #[cfg(feature = "brotli")]
+    pub fn brotli(mut self, enable: bool) -> Self {
+        self.brotli = enable;
+        self
+    }
+
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    pub fn check_message_code(mut self, check: bool) -> Self {
+        self.check_message_code = check;
+        self
+    }
+
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    pub fn build(self) -> Result<WlClient, WlError> {
+        let mut http = reqwest::blocking::Client::builder()
+            .user_agent(self.user_agent)
+            .default_headers(self.default_headers);
+        // reqwest applies this timeout to the response head and to each read of the body
+        if let Some(timeout) = self.read_timeout.or(self.timeout) {
+            http = http.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+        for proxy in self.proxies {
+            http = http.proxy(proxy);
+        }
+        #[cfg(any(feature = "native-tls", feature = "rustls"))]
+        {
+            for certificate in self.root_certificates {
+                http = http.add_root_certificate(certificate);
+            }
+            http = http.tls_built_in_root_certs(self.built_in_root_certificates);
+        }
+        #[cfg(feature = "gzip")]
+        {
+            http = http.gzip(self.gzip);
+        }
+        #[cfg(feature = "brotli")]
+        {
+            http = http.brotli(self.brotli);
+        }
+
+        Ok(WlClient {
+            http: http.build()?,
+            timeout: self.timeout,
+            base_url: self.base_url,
+            check_message_code: self.check_message_code,
+            retry_policy: self.retry_policy,
+        })
+    }
+}
+
+impl Default for WlClientBuilder {
+    fn default() -> Self {
+        WlClientBuilder::new()
+    }
+}
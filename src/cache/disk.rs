@@ -0,0 +1,167 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{unix_millis, CacheBackend, CacheResult, CachedResponse};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DiskEntry {
+    key: String,
+    #[serde(rename = "expiresAt")]
+    expires_at: u64,
+    response: CachedResponse,
+}
+
+/// File names of entries are `wl-<hash>.cache.json`, only those are removed by `clear`.
+const ENTRY_PREFIX: &str = "wl-";
+const ENTRY_SUFFIX: &str = ".cache.json";
+
+/// Backend storing one JSON file per entry in a local directory.
+///
+/// Several processes on the same host can share the directory. Other files in it are
+/// left alone. Expired entries are deleted when read and, at most once per
+/// `prune_interval`, by a scan of the directory when an entry is stored.
+#[derive(Debug, Clone)]
+pub struct DiskBackend {
+    dir: PathBuf,
+    prune_interval: Duration,
+    last_prune: Arc<Mutex<Option<Instant>>>,
+}
+
+impl DiskBackend {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        DiskBackend {
+            dir: dir.into(),
+            prune_interval: Duration::from_secs(60),
+            last_prune: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Minimum time between two scans for expired entries. One minute by default.
+    pub fn prune_interval(mut self, interval: Duration) -> Self {
+        self.prune_interval = interval;
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether the directory is due for a scan, marks it as scanned if so.
+    fn start_prune(&self) -> bool {
+        let mut last_prune = self.last_prune.lock().unwrap();
+        let due = last_prune.is_none_or(|last_prune| last_prune.elapsed() >= self.prune_interval);
+        if due {
+            *last_prune = Some(Instant::now());
+        }
+        due
+    }
+
+    /// Deletes the expired entries of all keys, also the ones written by other processes.
+    async fn prune(&self) -> CacheResult<()> {
+        let now = unix_millis(SystemTime::now());
+        let mut entries = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            if !entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(ENTRY_PREFIX) && name.ends_with(ENTRY_SUFFIX))
+            {
+                continue;
+            }
+            // entries that are unreadable, e.g. removed in the meantime, are skipped
+            let Ok(content) = tokio::fs::read(entry.path()).await else {
+                continue;
+            };
+            match serde_json::from_slice::<DiskEntry>(&content) {
+                Ok(disk_entry) if disk_entry.expires_at > now => {}
+                _ => {
+                    let _ = tokio::fs::remove_file(entry.path()).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{}{:016x}{}",
+            ENTRY_PREFIX,
+            fnv1a(key.as_bytes()),
+            ENTRY_SUFFIX
+        ))
+    }
+}
+
+/// Entries and temporary files of interrupted writes (`wl-<hash>.cache.<n>.tmp`).
+fn is_entry_file(name: &str) -> bool {
+    name.starts_with(ENTRY_PREFIX)
+        && (name.ends_with(ENTRY_SUFFIX) || (name.contains(".cache.") && name.ends_with(".tmp")))
+}
+
+/// Stable hash for file names, `DefaultHasher` may change between Rust releases.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[async_trait]
+impl CacheBackend for DiskBackend {
+    async fn get(&self, key: &str) -> CacheResult<Option<CachedResponse>> {
+        let path = self.path(key);
+        let content = match tokio::fs::read(&path).await {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let entry: DiskEntry = serde_json::from_slice(&content)?;
+        // hash collision
+        if entry.key != key {
+            return Ok(None);
+        }
+        if entry.expires_at <= unix_millis(SystemTime::now()) {
+            let _ = tokio::fs::remove_file(&path).await;
+            return Ok(None);
+        }
+        Ok(Some(entry.response))
+    }
+
+    async fn set(&self, key: &str, entry: &CachedResponse, retention: Duration) -> CacheResult<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let content = serde_json::to_vec(&DiskEntry {
+            key: key.to_owned(),
+            expires_at: unix_millis(entry.stored_at + retention),
+            response: entry.clone(),
+        })?;
+        // write to a temporary file first so readers never see partial entries
+        let path = self.path(key);
+        let tmp = path.with_extension(format!("{}.tmp", fastrand::u64(..)));
+        tokio::fs::write(&tmp, content).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+        if self.start_prune() {
+            let _ = self.prune().await;
+        }
+        Ok(())
+    }
+
+    async fn clear(&self) -> CacheResult<()> {
+        let mut entries = match tokio::fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            if entry.file_name().to_str().is_some_and(is_entry_file) {
+                tokio::fs::remove_file(entry.path()).await?;
+            }
+        }
+        Ok(())
+    }
+}
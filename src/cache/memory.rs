@@ -0,0 +1,95 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+
+use super::{CacheBackend, CacheResult, CachedResponse};
+
+#[derive(Debug)]
+struct Entry {
+    response: CachedResponse,
+    expires_at: SystemTime,
+    last_access: u64,
+}
+
+/// In-process backend with least recently used eviction.
+#[derive(Debug)]
+pub struct MemoryBackend {
+    max_entries: usize,
+    entries: Mutex<HashMap<String, Entry>>,
+    clock: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl MemoryBackend {
+    pub fn new(max_entries: usize) -> Self {
+        MemoryBackend {
+            max_entries: max_entries.max(1),
+            entries: Mutex::new(HashMap::new()),
+            clock: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryBackend {
+    async fn get(&self, key: &str) -> CacheResult<Option<CachedResponse>> {
+        let mut entries = self.entries.lock().unwrap();
+        let Some(entry) = entries.get_mut(key) else {
+            return Ok(None);
+        };
+        if entry.expires_at <= SystemTime::now() {
+            entries.remove(key);
+            return Ok(None);
+        }
+        entry.last_access = self.clock.fetch_add(1, Ordering::Relaxed);
+        Ok(Some(entry.response.clone()))
+    }
+
+    async fn set(&self, key: &str, entry: &CachedResponse, retention: Duration) -> CacheResult<()> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            key.to_owned(),
+            Entry {
+                response: entry.clone(),
+                expires_at: entry.stored_at + retention,
+                last_access: self.clock.fetch_add(1, Ordering::Relaxed),
+            },
+        );
+        while entries.len() > self.max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_access)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        Ok(())
+    }
+
+    async fn clear(&self) -> CacheResult<()> {
+        self.entries.lock().unwrap().clear();
+        Ok(())
+    }
+
+    fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    models::Response,
+    serde_deserializers::{date_format, system_time_millis},
+};
+
+mod disk;
+mod memory;
+#[cfg(feature = "redis")]
+mod redis;
+
+#[cfg(feature = "redis")]
+pub use self::redis::RedisBackend;
+pub use disk::DiskBackend;
+pub use memory::MemoryBackend;
+
+pub type CacheResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// API endpoint a cached response belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    Monitor,
+    TrafficInfoList,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    monitor_ttl: Duration,
+    traffic_info_list_ttl: Duration,
+    stale_while_revalidate: Duration,
+    pub(crate) max_entries: usize,
+}
+
+impl CacheConfig {
+    pub fn new() -> Self {
+        CacheConfig {
+            monitor_ttl: Duration::from_secs(15),
+            traffic_info_list_ttl: Duration::from_secs(60),
+            stale_while_revalidate: Duration::ZERO,
+            max_entries: 1000,
+        }
+    }
+
+    pub fn monitor_ttl(mut self, ttl: Duration) -> Self {
+        self.monitor_ttl = ttl;
+        self
+    }
+
+    pub fn traffic_info_list_ttl(mut self, ttl: Duration) -> Self {
+        self.traffic_info_list_ttl = ttl;
+        self
+    }
+
+    /// How long after expiry an entry is still served while it is refreshed in the background.
+    pub fn stale_while_revalidate(mut self, duration: Duration) -> Self {
+        self.stale_while_revalidate = duration;
+        self
+    }
+
+    /// Least recently used entries are evicted once this size is exceeded.
+    /// Only used by the default `MemoryBackend`.
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    pub fn ttl(&self, endpoint: Endpoint) -> Duration {
+        match endpoint {
+            Endpoint::Monitor => self.monitor_ttl,
+            Endpoint::TrafficInfoList => self.traffic_info_list_ttl,
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub stale_hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+/// A response body as received from the API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    /// Raw JSON body of the response.
+    pub body: String,
+    /// `message.serverTime` of the response.
+    #[serde(rename = "serverTime", with = "date_format")]
+    pub server_time: DateTime<FixedOffset>,
+    #[serde(rename = "storedAt", with = "system_time_millis")]
+    pub stored_at: SystemTime,
+}
+
+impl CachedResponse {
+    pub(crate) fn from_body(body: &[u8]) -> Option<Self> {
+        let response: Response = serde_json::from_slice(body).ok()?;
+        Some(CachedResponse {
+            body: String::from_utf8(body.to_vec()).ok()?,
+            server_time: response.message.server_time,
+            stored_at: SystemTime::now(),
+        })
+    }
+
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.stored_at)
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+/// Storage used by the client's response cache.
+///
+/// Keys are the output of `build_request_url()`. Errors of a backend are not fatal,
+/// the client treats them like a cache miss.
+#[async_trait]
+pub trait CacheBackend: Debug + Send + Sync {
+    async fn get(&self, key: &str) -> CacheResult<Option<CachedResponse>>;
+
+    /// Stores an entry. It is not needed by the client anymore after `retention`.
+    async fn set(&self, key: &str, entry: &CachedResponse, retention: Duration) -> CacheResult<()>;
+
+    async fn clear(&self) -> CacheResult<()>;
+
+    /// Number of entries the backend dropped to stay within its size limit.
+    fn evictions(&self) -> u64 {
+        0
+    }
+}
+
+pub(crate) fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis() as u64)
+}
+
+#[derive(Debug)]
+pub(crate) enum Lookup {
+    Fresh(Bytes),
+    /// Expired but within the stale-while-revalidate window. `true` if the caller
+    /// should start the revalidation.
+    Stale(Bytes, bool),
+    Miss,
+}
+
+/// Response cache of a client, adds expiry and statistics on top of a backend.
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    config: CacheConfig,
+    backend: Arc<dyn CacheBackend>,
+    revalidating: Mutex<HashSet<String>>,
+    hits: AtomicU64,
+    stale_hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: CacheConfig, backend: Arc<dyn CacheBackend>) -> Self {
+        ResponseCache {
+            config,
+            backend,
+            revalidating: Mutex::new(HashSet::new()),
+            hits: AtomicU64::new(0),
+            stale_hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) async fn lookup(&self, key: &str, endpoint: Endpoint) -> Lookup {
+        let ttl = self.config.ttl(endpoint);
+        let Ok(Some(entry)) = self.backend.get(key).await else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return Lookup::Miss;
+        };
+
+        let age = entry.age();
+        if age < ttl {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            Lookup::Fresh(Bytes::from(entry.body))
+        } else if age < ttl + self.config.stale_while_revalidate {
+            self.stale_hits.fetch_add(1, Ordering::Relaxed);
+            let revalidate = self.revalidating.lock().unwrap().insert(key.to_owned());
+            Lookup::Stale(Bytes::from(entry.body), revalidate)
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            Lookup::Miss
+        }
+    }
+
+    pub(crate) async fn insert(&self, key: &str, endpoint: Endpoint, body: &[u8]) {
+        let Some(entry) = CachedResponse::from_body(body) else {
+            return;
+        };
+        let retention = self.config.ttl(endpoint) + self.config.stale_while_revalidate;
+        let _ = self.backend.set(key, &entry, retention).await;
+    }
+
+    pub(crate) fn revalidated(&self, key: &str) {
+        self.revalidating.lock().unwrap().remove(key);
+    }
+
+    pub(crate) async fn clear(&self) {
+        let _ = self.backend.clear().await;
+    }
+
+    pub(crate) fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            stale_hits: self.stale_hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.backend.evictions(),
+        }
+    }
+}
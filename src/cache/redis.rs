@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::{aio::MultiplexedConnection, AsyncCommands, RedisError};
+use tokio::sync::Mutex;
+
+use super::{CacheBackend, CacheResult, CachedResponse};
+
+/// Backend storing entries in Redis, shared by all processes using the same server.
+///
+/// Entries expire in Redis after their retention time. The connection is re-established
+/// after connection errors, e.g. when the server restarts.
+#[derive(Debug)]
+pub struct RedisBackend {
+    client: redis::Client,
+    prefix: String,
+    connection: Mutex<Option<MultiplexedConnection>>,
+}
+
+/// Number of keys requested per `SCAN` call by `clear`.
+const SCAN_COUNT: usize = 1000;
+
+impl RedisBackend {
+    pub fn new(url: &str) -> CacheResult<Self> {
+        Ok(RedisBackend {
+            client: redis::Client::open(url)?,
+            prefix: String::from("wl-realtime-ogd:"),
+            connection: Mutex::new(None),
+        })
+    }
+
+    /// Prefix prepended to every key, defaults to `wl-realtime-ogd:`.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    async fn connection(&self) -> CacheResult<MultiplexedConnection> {
+        let mut connection = self.connection.lock().await;
+        if let Some(connection) = connection.as_ref() {
+            return Ok(connection.clone());
+        }
+        let created = self.client.get_multiplexed_async_connection().await?;
+        *connection = Some(created.clone());
+        Ok(created)
+    }
+
+    /// Drops the cached connection if the error means it is broken, the next call reconnects.
+    async fn check<T>(&self, result: Result<T, RedisError>) -> CacheResult<T> {
+        if let Err(err) = &result {
+            if err.is_unrecoverable_error() || err.is_io_error() || err.is_connection_dropped() {
+                *self.connection.lock().await = None;
+            }
+        }
+        Ok(result?)
+    }
+}
+
+#[async_trait]
+impl CacheBackend for RedisBackend {
+    async fn get(&self, key: &str) -> CacheResult<Option<CachedResponse>> {
+        let mut connection = self.connection().await?;
+        let value: Option<String> = self
+            .check(connection.get(format!("{}{}", self.prefix, key)).await)
+            .await?;
+        match value {
+            Some(value) => Ok(Some(serde_json::from_str(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, entry: &CachedResponse, retention: Duration) -> CacheResult<()> {
+        let mut connection = self.connection().await?;
+        let value = serde_json::to_string(entry)?;
+        let millis = retention.as_millis().max(1) as u64;
+        let result: Result<(), RedisError> = redis::cmd("SET")
+            .arg(format!("{}{}", self.prefix, key))
+            .arg(value)
+            .arg("PX")
+            .arg(millis)
+            .query_async(&mut connection)
+            .await;
+        self.check(result).await
+    }
+
+    /// Deletes all keys with the prefix, iterating with `SCAN` to not block the server.
+    async fn clear(&self) -> CacheResult<()> {
+        let mut connection = self.connection().await?;
+        let pattern = format!("{}*", self.prefix);
+        let mut cursor: u64 = 0;
+        loop {
+            let result: Result<(u64, Vec<String>), RedisError> = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(SCAN_COUNT)
+                .query_async(&mut connection)
+                .await;
+            let (next, keys) = self.check(result).await?;
+            if !keys.is_empty() {
+                let result: Result<(), RedisError> = connection.del(keys).await;
+                self.check(result).await?;
+            }
+            if next == 0 {
+                return Ok(());
+            }
+            cursor = next;
+        }
+    }
+}
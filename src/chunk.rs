@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::{
+    client::WlClient,
+    error::WlError,
+    models::{MonitorRequest, MonitorResponse, MAX_STOP_IDS},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkConfig {
+    chunk_size: usize,
+    concurrency: usize,
+}
+
+impl ChunkConfig {
+    pub fn new() -> Self {
+        ChunkConfig {
+            chunk_size: MAX_STOP_IDS,
+            concurrency: 4,
+        }
+    }
+
+    /// Maximum number of stop IDs per HTTP call.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Maximum number of chunks in flight at the same time.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+}
+
+impl Default for ChunkConfig {
+    fn default() -> Self {
+        ChunkConfig::new()
+    }
+}
+
+impl WlClient {
+    /// Sends a request with a large stop list as several smaller requests and merges the results.
+    ///
+    /// Monitors are returned in the order of the chunks. Traffic infos and their categories
+    /// are deduplicated. Fails with the first error encountered.
+    pub async fn monitor_chunked(
+        &self,
+        request: &MonitorRequest,
+        config: ChunkConfig,
+    ) -> Result<MonitorResponse, WlError> {
+        if request.stop_id.len() <= config.chunk_size {
+            return self.monitor(request).await;
+        }
+
+        let semaphore = Arc::new(Semaphore::new(config.concurrency));
+        let mut tasks = JoinSet::new();
+        for (idx, stop_ids) in request.stop_id.chunks(config.chunk_size).enumerate() {
+            let mut chunk = request.clone();
+            chunk.stop_id = stop_ids.to_vec();
+            if idx > 0 {
+                chunk.diva = None;
+            }
+            let client = self.clone();
+            let semaphore = semaphore.clone();
+            tasks.spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+                (idx, client.monitor(&chunk).await)
+            });
+        }
+
+        let mut responses = vec![];
+        while let Some(joined) = tasks.join_next().await {
+            let (idx, result) = joined.expect("chunk task should not panic");
+            responses.push((idx, result?));
+        }
+        responses.sort_by_key(|(idx, _)| *idx);
+
+        let mut responses = responses.into_iter().map(|(_, response)| response);
+        let mut merged = responses.next().expect("at least two chunks were sent");
+        for response in responses {
+            merged.data.merge(response.data);
+        }
+        Ok(merged)
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{error::WlError, models::MessageCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests are sent normally.
+    Closed,
+    /// Requests fail fast with `WlError::CircuitOpen` until the cool-down has passed.
+    Open,
+    /// The cool-down has passed, a limited number of probe requests decide whether to close again.
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    failure_threshold: u32,
+    cool_down: Duration,
+    half_open_max_calls: u32,
+}
+
+impl CircuitBreakerConfig {
+    pub fn new() -> Self {
+        CircuitBreakerConfig {
+            failure_threshold: 5,
+            cool_down: Duration::from_secs(30),
+            half_open_max_calls: 1,
+        }
+    }
+
+    /// Number of consecutive failures that open the circuit.
+    pub fn failure_threshold(mut self, threshold: u32) -> Self {
+        self.failure_threshold = threshold.max(1);
+        self
+    }
+
+    /// How long the circuit stays open before probe requests are allowed.
+    pub fn cool_down(mut self, cool_down: Duration) -> Self {
+        self.cool_down = cool_down;
+        self
+    }
+
+    /// Number of concurrent probe requests while half-open.
+    pub fn half_open_max_calls(mut self, calls: u32) -> Self {
+        self.half_open_max_calls = calls.max(1);
+        self
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        CircuitBreakerConfig::new()
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    half_open_calls: u32,
+}
+
+/// Stops sending requests to an upstream that keeps failing.
+///
+/// Transport errors, 5xx statuses and `MessageCode::DbOffline` count as failures,
+/// every other outcome shows the upstream is healthy.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        CircuitBreaker {
+            config,
+            inner: Arc::new(Mutex::new(Inner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                half_open_calls: 0,
+            })),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let mut inner = self.inner.lock().unwrap();
+        self.update_state(&mut inner);
+        inner.state
+    }
+
+    /// Asks for permission to send a request.
+    pub(crate) fn acquire(&self) -> Result<CircuitPermit, WlError> {
+        let mut inner = self.inner.lock().unwrap();
+        self.update_state(&mut inner);
+        match inner.state {
+            CircuitState::Closed => Ok(self.permit(false)),
+            CircuitState::HalfOpen if inner.half_open_calls < self.config.half_open_max_calls => {
+                inner.half_open_calls += 1;
+                Ok(self.permit(true))
+            }
+            CircuitState::HalfOpen => Err(WlError::CircuitOpen {
+                retry_after: Duration::ZERO,
+            }),
+            CircuitState::Open => {
+                let elapsed = inner.opened_at.map_or(Duration::ZERO, |at| at.elapsed());
+                Err(WlError::CircuitOpen {
+                    retry_after: self.config.cool_down.saturating_sub(elapsed),
+                })
+            }
+        }
+    }
+
+    fn permit(&self, probe: bool) -> CircuitPermit {
+        CircuitPermit {
+            inner: self.inner.clone(),
+            failure_threshold: self.config.failure_threshold,
+            probe,
+            reported: false,
+        }
+    }
+
+    fn update_state(&self, inner: &mut Inner) {
+        if inner.state == CircuitState::Open
+            && inner
+                .opened_at
+                .is_some_and(|at| at.elapsed() >= self.config.cool_down)
+        {
+            inner.state = CircuitState::HalfOpen;
+            inner.half_open_calls = 0;
+        }
+    }
+
+    pub(crate) fn is_failure(error: &WlError) -> bool {
+        match error {
+            WlError::Transport(_) => true,
+            WlError::HttpStatus { status, .. } => *status >= 500,
+            WlError::Api { code, .. } => *code == MessageCode::DbOffline,
+            WlError::Decode { .. } | WlError::RateLimited { .. } | WlError::CircuitOpen { .. } => {
+                false
+            }
+        }
+    }
+}
+
+/// Permission to send one request, reports its outcome back to the breaker.
+pub(crate) struct CircuitPermit {
+    inner: Arc<Mutex<Inner>>,
+    failure_threshold: u32,
+    probe: bool,
+    reported: bool,
+}
+
+impl CircuitPermit {
+    pub(crate) fn report<T>(mut self, result: &Result<T, WlError>) {
+        self.reported = true;
+        let mut inner = self.inner.lock().unwrap();
+        if self.probe {
+            inner.half_open_calls = inner.half_open_calls.saturating_sub(1);
+        }
+        // the request never reached the upstream, so it says nothing about its health
+        if matches!(
+            result,
+            Err(WlError::RateLimited { .. } | WlError::CircuitOpen { .. })
+        ) {
+            return;
+        }
+        let failed = matches!(result, Err(err) if CircuitBreaker::is_failure(err));
+        if !failed {
+            inner.state = CircuitState::Closed;
+            inner.consecutive_failures = 0;
+            inner.opened_at = None;
+            return;
+        }
+
+        inner.consecutive_failures += 1;
+        if inner.state == CircuitState::HalfOpen
+            || inner.consecutive_failures >= self.failure_threshold
+        {
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+impl Drop for CircuitPermit {
+    fn drop(&mut self) {
+        // a cancelled probe must not keep the circuit half-open forever
+        if !self.reported && self.probe {
+            let mut inner = self.inner.lock().unwrap();
+            inner.half_open_calls = inner.half_open_calls.saturating_sub(1);
+        }
+    }
+}
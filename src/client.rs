@@ -0,0 +1,585 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
+use serde::de::DeserializeOwned;
+use tokio::runtime::{self, Handle};
+
+use crate::{
+    cache::{
+        CacheBackend, CacheConfig, CacheStats, Endpoint, Lookup, MemoryBackend, ResponseCache,
+    },
+    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState},
+    error::WlError,
+    models::{
+        BuildRequestUrl, MonitorRequest, MonitorResponse, Response, TrafficInfoListRequest,
+        TrafficInfoListResponse,
+    },
+    rate_limit::{RateLimit, RateLimiter},
+    retry::{parse_retry_after, RetryPolicy},
+    single_flight::{self, Flight, SingleFlight},
+    transport::{HttpTransport, ReqwestTransport},
+};
+
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+pub use reqwest::Certificate;
+pub use reqwest::Proxy;
+
+pub const WL_ENDPOINT: &str = "https://www.wienerlinien.at/ogd_realtime";
+
+pub(crate) const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// Client for the WL real-time API.
+///
+/// Cloning is cheap, all clones share the same transport and connection pool.
+#[derive(Debug, Clone)]
+pub struct WlClient {
+    transport: Arc<dyn HttpTransport>,
+    base_url: String,
+    check_message_code: bool,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<Arc<ResponseCache>>,
+    single_flight: Option<Arc<SingleFlight>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+}
+
+impl WlClient {
+    pub fn new() -> Self {
+        WlClient::builder()
+            .build()
+            .expect("default client configuration should always be valid")
+    }
+
+    pub fn builder() -> WlClientBuilder {
+        WlClientBuilder::new()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub async fn monitor(&self, request: &MonitorRequest) -> Result<MonitorResponse, WlError> {
+        self.get(request).await
+    }
+
+    pub async fn traffic_info_list(
+        &self,
+        request: &TrafficInfoListRequest,
+    ) -> Result<TrafficInfoListResponse, WlError> {
+        self.get(request).await
+    }
+
+    /// Statistics of the response cache, `None` if caching is disabled.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// Number of distinct requests currently being sent.
+    pub fn in_flight_requests(&self) -> usize {
+        self.single_flight
+            .as_ref()
+            .map_or(0, |flights| flights.len())
+    }
+
+    /// State of the circuit breaker, `None` if it is disabled.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit_breaker.as_ref().map(|breaker| breaker.state())
+    }
+
+    pub async fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear().await;
+        }
+    }
+
+    async fn get<T: ApiResponse>(&self, request: &impl BuildRequestUrl) -> Result<T, WlError> {
+        // the base URL is part of the cache key, clients of different servers may share a backend
+        let url = self.base_url.clone() + &request.build_request_url();
+        let Some(cache) = &self.cache else {
+            return self.fetch_shared::<T>(&url).await;
+        };
+
+        match cache.lookup(&url, T::ENDPOINT).await {
+            Lookup::Fresh(body) => return parse_body(&body),
+            Lookup::Stale(body, revalidate) => {
+                if revalidate {
+                    let client = self.clone();
+                    let url = url.clone();
+                    tokio::spawn(async move {
+                        let _ = client.fetch_shared::<T>(&url).await;
+                        if let Some(cache) = &client.cache {
+                            cache.revalidated(&url);
+                        }
+                    });
+                }
+                return parse_body(&body);
+            }
+            Lookup::Miss => {}
+        }
+        self.fetch_shared(&url).await
+    }
+
+    async fn fetch_shared<T: ApiResponse>(&self, url: &str) -> Result<T, WlError> {
+        let Some(single_flight) = &self.single_flight else {
+            return self
+                .fetch_and_store(url)
+                .await
+                .map(|(response, _)| response);
+        };
+        match single_flight.join(url) {
+            Flight::Leader(guard) => {
+                let result = self.fetch_and_store::<T>(url).await;
+                guard.complete(match &result {
+                    Ok((_, body)) => Ok(body.clone()),
+                    Err(err) => Err(err.clone()),
+                });
+                result.map(|(response, _)| response)
+            }
+            Flight::Follower(receiver) => match single_flight::wait(receiver).await {
+                Some(result) => result.and_then(|body| parse_body(&body)),
+                // the leader was cancelled, send the request ourselves
+                None => self
+                    .fetch_and_store(url)
+                    .await
+                    .map(|(response, _)| response),
+            },
+        }
+    }
+
+    async fn fetch_and_store<T: ApiResponse>(&self, url: &str) -> Result<(T, Bytes), WlError> {
+        let (response, body) = self.fetch::<T>(url).await?;
+        if let Some(cache) = &self.cache {
+            if response.is_success() {
+                cache.insert(url, T::ENDPOINT, &body).await;
+            }
+        }
+        Ok((response, body))
+    }
+
+    async fn fetch<T: ApiResponse>(&self, url: &str) -> Result<(T, Bytes), WlError> {
+        let mut attempt = 1;
+        loop {
+            let (result, retry_after) = self.attempt(url).await;
+            match result {
+                Err(err)
+                    if attempt < self.retry_policy.max_attempts
+                        && self.retry_policy.is_retryable(&err) =>
+                {
+                    tokio::time::sleep(self.retry_policy.delay(attempt, retry_after)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn attempt<T: ApiResponse>(
+        &self,
+        url: &str,
+    ) -> (Result<(T, Bytes), WlError>, Option<Duration>) {
+        // wait for the limiter first, a rejected or waiting request must not hold a probe permit
+        if let Some(limiter) = &self.rate_limiter {
+            if let Err(err) = limiter.acquire().await {
+                return (Err(err), None);
+            }
+        }
+        let permit = match self
+            .circuit_breaker
+            .as_ref()
+            .map(|breaker| breaker.acquire())
+        {
+            Some(Ok(permit)) => Some(permit),
+            Some(Err(err)) => return (Err(err), None),
+            None => None,
+        };
+        let (result, retry_after) = self.send::<T>(url).await;
+        if let Some(permit) = permit {
+            permit.report(&result);
+        }
+        (result, retry_after)
+    }
+
+    async fn send<T: ApiResponse>(
+        &self,
+        url: &str,
+    ) -> (Result<(T, Bytes), WlError>, Option<Duration>) {
+        let response = match self.transport.get(url).await {
+            Ok(response) => response,
+            Err(err) => return (Err(err), None),
+        };
+        let retry_after = response
+            .header(RETRY_AFTER.as_str())
+            .and_then(parse_retry_after);
+        let result = decode_response(response.status, response.body, self.check_message_code);
+        (result, retry_after)
+    }
+}
+
+impl Default for WlClient {
+    fn default() -> Self {
+        WlClient::new()
+    }
+}
+
+pub(crate) trait ApiResponse: DeserializeOwned + Send + 'static {
+    const ENDPOINT: Endpoint;
+
+    fn is_success(&self) -> bool;
+
+    fn into_result(self) -> Result<Self, WlError>;
+}
+
+impl ApiResponse for MonitorResponse {
+    const ENDPOINT: Endpoint = Endpoint::Monitor;
+
+    fn is_success(&self) -> bool {
+        self.message.is_ok()
+    }
+
+    fn into_result(self) -> Result<Self, WlError> {
+        MonitorResponse::into_result(self)
+    }
+}
+
+impl ApiResponse for TrafficInfoListResponse {
+    const ENDPOINT: Endpoint = Endpoint::TrafficInfoList;
+
+    fn is_success(&self) -> bool {
+        self.message.is_ok()
+    }
+
+    fn into_result(self) -> Result<Self, WlError> {
+        TrafficInfoListResponse::into_result(self)
+    }
+}
+
+/// Turns a raw HTTP response into the parsed model, shared by the async and blocking clients.
+pub(crate) fn decode_response<T: ApiResponse>(
+    status: u16,
+    body: Bytes,
+    check_message_code: bool,
+) -> Result<(T, Bytes), WlError> {
+    if !(200..300).contains(&status) {
+        return Err(WlError::http_status(
+            status,
+            &String::from_utf8_lossy(&body),
+        ));
+    }
+
+    let response = parse_body::<T>(&body).map_err(|err| api_error(&body).unwrap_or(err))?;
+    let response = if check_message_code {
+        response.into_result()?
+    } else {
+        response
+    };
+    Ok((response, body))
+}
+
+pub(crate) fn parse_body<T: DeserializeOwned>(body: &[u8]) -> Result<T, WlError> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+    serde_path_to_error::deserialize(deserializer).map_err(WlError::decode)
+}
+
+/// Recovers the `Message` of error-shaped bodies whose `data` section does not match the model.
+fn api_error(body: &[u8]) -> Option<WlError> {
+    let response: Response = serde_json::from_slice(body).ok()?;
+    response.message.into_result().err()
+}
+
+struct DefaultClient {
+    builder: Option<WlClientBuilder>,
+    client: Option<(runtime::Id, WlClient)>,
+}
+
+static DEFAULT_CLIENT: Mutex<DefaultClient> = Mutex::new(DefaultClient {
+    builder: None,
+    client: None,
+});
+
+/// Configures the client used by the `run()` convenience methods of the requests, e.g.
+/// to point them at a `MockWlServer` in tests.
+pub fn set_default_client(builder: WlClientBuilder) {
+    let mut default = DEFAULT_CLIENT.lock().unwrap();
+    default.builder = Some(builder);
+    default.client = None;
+}
+
+/// Client used by the `run()` convenience methods of the requests.
+///
+/// Pooled connections only work on the runtime that opened them, so the client is
+/// rebuilt whenever `run()` is called from another runtime than the previous call.
+/// Long-running programs should create and keep their own `WlClient` instead.
+pub(crate) fn default_client() -> Result<WlClient, WlError> {
+    let runtime = Handle::current().id();
+    let mut default = DEFAULT_CLIENT.lock().unwrap();
+    if let Some((id, client)) = &default.client {
+        if *id == runtime {
+            return Ok(client.clone());
+        }
+    }
+    let client = match &default.builder {
+        Some(builder) => builder.clone().build()?,
+        None => WlClient::new(),
+    };
+    default.client = Some((runtime, client.clone()));
+    Ok(client)
+}
+
+#[derive(Debug, Clone)]
+pub struct WlClientBuilder {
+    base_url: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    user_agent: String,
+    default_headers: HeaderMap,
+    proxies: Vec<Proxy>,
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    root_certificates: Vec<Certificate>,
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    built_in_root_certificates: bool,
+    #[cfg(feature = "gzip")]
+    gzip: bool,
+    #[cfg(feature = "brotli")]
+    brotli: bool,
+    check_message_code: bool,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    cache: Option<(CacheConfig, Arc<dyn CacheBackend>)>,
+    deduplicate_requests: bool,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    transport: Option<Arc<dyn HttpTransport>>,
+}
+
+impl WlClientBuilder {
+    pub fn new() -> Self {
+        WlClientBuilder {
+            base_url: String::from(WL_ENDPOINT),
+            timeout: None,
+            connect_timeout: None,
+            read_timeout: None,
+            user_agent: String::from(DEFAULT_USER_AGENT),
+            default_headers: HeaderMap::new(),
+            proxies: vec![],
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            root_certificates: vec![],
+            #[cfg(any(feature = "native-tls", feature = "rustls"))]
+            built_in_root_certificates: true,
+            #[cfg(feature = "gzip")]
+            gzip: true,
+            #[cfg(feature = "brotli")]
+            brotli: true,
+            check_message_code: true,
+            retry_policy: RetryPolicy::none(),
+            rate_limiter: None,
+            cache: None,
+            deduplicate_requests: true,
+            circuit_breaker: None,
+            transport: None,
+        }
+    }
+
+    /// Base URL the request paths are appended to, e.g. a staging mirror or a local stand-in.
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url: String = base_url.into();
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum time to wait for the response head and for each chunk of the body.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Sends requests through an HTTP(S) proxy. Can be called multiple times, the first
+    /// matching proxy is used. Without one the system proxy settings apply.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Trusts an additional root certificate, e.g. of a corporate TLS-intercepting proxy.
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Whether the built-in root certificates are trusted. Enabled by default.
+    #[cfg(any(feature = "native-tls", feature = "rustls"))]
+    pub fn built_in_root_certificates(mut self, enable: bool) -> Self {
+        self.built_in_root_certificates = enable;
+        self
+    }
+
+    /// Whether gzip compressed responses are requested and decompressed. Enabled by default.
+    #[cfg(feature = "gzip")]
+    pub fn gzip(mut self, enable: bool) -> Self {
+        self.gzip = enable;
+        self
+    }
+
+    /// Whether brotli compressed responses are requested and decompressed. Enabled by default.
+    #[cfg(feature = "brotli")]
+    pub fn brotli(mut self, enable: bool) -> Self {
+        self.brotli = enable;
+        self
+    }
+
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
+    /// Whether responses with a non-`OK` `MessageCode` are returned as `WlError::Api`.
+    /// Enabled by default.
+    pub fn check_message_code(mut self, check: bool) -> Self {
+        self.check_message_code = check;
+        self
+    }
+
+    /// Retry policy for transient failures. By default requests are not retried.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Limits the requests issued through the client and all of its clones.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limiter = Some(Arc::new(RateLimiter::new(limit)));
+        self
+    }
+
+    /// Shares an existing limiter, e.g. between clients with different configurations.
+    pub fn rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Caches successful responses in memory, keyed by the request URL.
+    pub fn cache(self, config: CacheConfig) -> Self {
+        let backend = MemoryBackend::new(config.max_entries);
+        self.cache_backend(config, Arc::new(backend))
+    }
+
+    /// Caches successful responses in the given backend, e.g. to share them between processes.
+    /// Entries are keyed by the full request URL, so clients of different base URLs can
+    /// share a backend.
+    pub fn cache_backend(mut self, config: CacheConfig, backend: Arc<dyn CacheBackend>) -> Self {
+        self.cache = Some((config, backend));
+        self
+    }
+
+    /// Whether concurrent identical requests share a single HTTP call. Enabled by default.
+    pub fn deduplicate_requests(mut self, deduplicate: bool) -> Self {
+        self.deduplicate_requests = deduplicate;
+        self
+    }
+
+    /// Fails fast with `WlError::CircuitOpen` while the upstream keeps failing.
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Sends requests through a custom transport instead of `reqwest`. The timeouts, user agent,
+    /// default headers, proxy and TLS settings of the builder are not applied to it.
+    pub fn transport(mut self, transport: Arc<dyn HttpTransport>) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    pub fn build(self) -> Result<WlClient, WlError> {
+        let transport = match self.transport {
+            Some(transport) => transport,
+            None => {
+                let mut transport = ReqwestTransport::new(self.reqwest_client()?);
+                if let Some(timeout) = self.read_timeout {
+                    transport = transport.read_timeout(timeout);
+                }
+                Arc::new(transport)
+            }
+        };
+
+        Ok(WlClient {
+            transport,
+            base_url: self.base_url,
+            check_message_code: self.check_message_code,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter,
+            cache: self
+                .cache
+                .map(|(config, backend)| Arc::new(ResponseCache::new(config, backend))),
+            single_flight: self
+                .deduplicate_requests
+                .then(|| Arc::new(SingleFlight::default())),
+            circuit_breaker: self
+                .circuit_breaker
+                .map(|config| Arc::new(CircuitBreaker::new(config))),
+        })
+    }
+
+    fn reqwest_client(&self) -> Result<reqwest::Client, WlError> {
+        let mut http = reqwest::Client::builder()
+            .user_agent(&self.user_agent)
+            .default_headers(self.default_headers.clone());
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            http = http.connect_timeout(timeout);
+        }
+        for proxy in &self.proxies {
+            http = http.proxy(proxy.clone());
+        }
+        #[cfg(any(feature = "native-tls", feature = "rustls"))]
+        {
+            for certificate in &self.root_certificates {
+                http = http.add_root_certificate(certificate.clone());
+            }
+            http = http.tls_built_in_root_certs(self.built_in_root_certificates);
+        }
+        #[cfg(feature = "gzip")]
+        {
+            http = http.gzip(self.gzip);
+        }
+        #[cfg(feature = "brotli")]
+        {
+            http = http.brotli(self.brotli);
+        }
+
+        Ok(http.build()?)
+    }
+}
+
+impl Default for WlClientBuilder {
+    fn default() -> Self {
+        WlClientBuilder::new()
+    }
+}
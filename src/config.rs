@@ -0,0 +1,121 @@
+//! Named sets of requests kept in configuration, e.g. the stops a service monitors.
+//!
+//! ```json
+//! {
+//!     "westbahnhof": {
+//!         "monitor": [{ "stopId": [4431, 4432], "activateTrafficInfo": ["stoerunglang"] }]
+//!     },
+//!     "elevators": {
+//!         "trafficInfoList": [{ "name": ["aufzugsinfo"], "relatedLine": ["U3"] }]
+//!     }
+//! }
+//! ```
+//!
+//! JSON is read by `RequestConfig::from_file`, as the crate already depends on `serde_json`
+//! for the API responses. To not pull in further parsers, other formats such as TOML or
+//! YAML are read by passing the parse function of the application's crate:
+//!
+//! ```ignore
+//! let config = RequestConfig::from_file_with("requests.toml", toml::from_str)?;
+//! let config = RequestConfig::from_file_with("requests.yaml", serde_yaml::from_str)?;
+//! ```
+//!
+//! All types implement `Deserialize`, so they can also be part of a larger configuration.
+
+use std::{collections::HashMap, error::Error, path::Path};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{
+    error::{ConfigError, RequestValidationError},
+    models::{MonitorRequest, TrafficInfoListRequest},
+};
+
+/// Requests sent together, either list may be empty.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestSet {
+    pub monitor: Vec<MonitorRequest>,
+    #[serde(rename = "trafficInfoList")]
+    pub traffic_info_list: Vec<TrafficInfoListRequest>,
+}
+
+impl RequestSet {
+    /// Validates every request of the set, see `MonitorRequest::validate`.
+    pub fn validate(&self) -> Result<(), RequestValidationError> {
+        self.monitor.iter().try_for_each(MonitorRequest::validate)?;
+        self.traffic_info_list
+            .iter()
+            .try_for_each(TrafficInfoListRequest::validate)
+    }
+}
+
+/// Request sets by name.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RequestConfig {
+    pub sets: HashMap<String, RequestSet>,
+}
+
+impl RequestConfig {
+    /// Reads a JSON configuration file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        RequestConfig::from_file_with(path, |content| serde_json::from_str(content))
+    }
+
+    /// Reads a configuration file with the given parse function, e.g. `toml::from_str`.
+    pub fn from_file_with<E>(
+        path: impl AsRef<Path>,
+        parse: impl FnOnce(&str) -> Result<Self, E>,
+    ) -> Result<Self, ConfigError>
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let content = std::fs::read_to_string(path)?;
+        parse(&content).map_err(|err| ConfigError::Parse(Box::new(err)))
+    }
+
+    /// Reads the configuration from a deserializer of any serde format.
+    pub fn from_deserializer<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        RequestConfig::deserialize(deserializer)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RequestSet> {
+        self.sets.get(name)
+    }
+
+    /// The set with the given name, after validating its requests.
+    pub fn request_set(&self, name: &str) -> Result<&RequestSet, ConfigError> {
+        let set = self
+            .get(name)
+            .ok_or_else(|| ConfigError::UnknownSet(name.to_owned()))?;
+        set.validate()
+            .map_err(|source| ConfigError::InvalidRequest {
+                set: name.to_owned(),
+                source,
+            })?;
+        Ok(set)
+    }
+}
+
+/// Loads the request set `name` from a JSON configuration file and validates it.
+pub fn load_request_set(path: impl AsRef<Path>, name: &str) -> Result<RequestSet, ConfigError> {
+    RequestConfig::from_file(path)?.request_set(name).cloned()
+}
+
+/// Like `load_request_set`, for files of other formats, see `RequestConfig::from_file_with`.
+pub fn load_request_set_with<E>(
+    path: impl AsRef<Path>,
+    name: &str,
+    parse: impl FnOnce(&str) -> Result<RequestConfig, E>,
+) -> Result<RequestSet, ConfigError>
+where
+    E: Error + Send + Sync + 'static,
+{
+    RequestConfig::from_file_with(path, parse)?
+        .request_set(name)
+        .cloned()
+}
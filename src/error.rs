@@ -0,0 +1,234 @@
+use std::{error::Error, fmt::Display, sync::Arc, time::Duration};
+
+use crate::models::MessageCode;
+
+#[cfg(feature = "client")]
+const BODY_SNIPPET_LEN: usize = 256;
+
+/// Errors returned by the client.
+///
+/// Cloneable so a single failure can be handed to every caller sharing a request.
+#[derive(Debug, Clone)]
+pub enum WlError {
+    /// The request could not be sent or the response could not be read (DNS, connect, timeout, ...).
+    Transport(Arc<dyn Error + Send + Sync>),
+    /// The server answered with a non-success HTTP status.
+    HttpStatus { status: u16, body: String },
+    /// The response body is not valid JSON or does not match the expected model.
+    Decode {
+        path: String,
+        source: Arc<serde_json::Error>,
+    },
+    /// The API answered with a `MessageCode` other than `OK`.
+    Api { code: MessageCode, message: String },
+    /// The client-side rate limiter has no budget left (fail-fast mode only).
+    RateLimited { retry_after: Duration },
+    /// The circuit breaker is open because the upstream kept failing.
+    CircuitOpen { retry_after: Duration },
+}
+
+impl WlError {
+    #[cfg(feature = "client")]
+    pub(crate) fn http_status(status: u16, body: &str) -> Self {
+        let body = match body.char_indices().nth(BODY_SNIPPET_LEN) {
+            Some((idx, _)) => format!("{}...", &body[..idx]),
+            None => body.to_owned(),
+        };
+        WlError::HttpStatus { status, body }
+    }
+
+    /// Wraps a failure of an `HttpTransport` to send a request or read its response.
+    pub fn transport(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        WlError::Transport(Arc::from(error.into()))
+    }
+
+    #[cfg(feature = "client")]
+    pub(crate) fn decode(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        WlError::Decode {
+            path: error.path().to_string(),
+            source: Arc::new(error.into_inner()),
+        }
+    }
+}
+
+impl Display for WlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WlError::Transport(err) => write!(f, "transport error: {}", err),
+            WlError::HttpStatus { status, body } => {
+                write!(f, "unexpected HTTP status {}: {}", status, body)
+            }
+            WlError::Decode { path, source } => {
+                write!(f, "failed to decode response at `{}`: {}", path, source)
+            }
+            WlError::Api { code, message } => {
+                write!(f, "API error {}: {}", code, message)
+            }
+            WlError::RateLimited { retry_after } => {
+                write!(f, "rate limit exceeded, retry after {:?}", retry_after)
+            }
+            WlError::CircuitOpen { retry_after } => {
+                write!(f, "circuit breaker is open, retry after {:?}", retry_after)
+            }
+        }
+    }
+}
+
+impl Error for WlError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            WlError::Transport(err) => Some(err.as_ref()),
+            WlError::Decode { source, .. } => Some(source.as_ref()),
+            WlError::HttpStatus { .. }
+            | WlError::Api { .. }
+            | WlError::RateLimited { .. }
+            | WlError::CircuitOpen { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+impl From<reqwest::Error> for WlError {
+    fn from(err: reqwest::Error) -> Self {
+        WlError::transport(err)
+    }
+}
+
+/// Errors parsing a request from its URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRequestError {
+    /// The string is not a valid URL or path.
+    InvalidUrl(String),
+    /// The path does not belong to the request type, e.g. `/trafficInfoList` for a `MonitorRequest`.
+    UnexpectedPath(String),
+    /// A query parameter not supported by the request type.
+    UnknownParameter(String),
+    /// A parameter that may only be given once was repeated.
+    DuplicateParameter(String),
+    /// A parameter value could not be parsed, e.g. a non-numeric `stopId`.
+    InvalidValue { parameter: String, value: String },
+    /// Not one of the lowercase traffic info names of the API.
+    UnknownTrafficInfo(String),
+}
+
+impl Display for ParseRequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseRequestError::InvalidUrl(err) => write!(f, "invalid URL: {}", err),
+            ParseRequestError::UnexpectedPath(path) => write!(f, "unexpected path `{}`", path),
+            ParseRequestError::UnknownParameter(parameter) => {
+                write!(f, "unknown parameter `{}`", parameter)
+            }
+            ParseRequestError::DuplicateParameter(parameter) => {
+                write!(f, "parameter `{}` may only be given once", parameter)
+            }
+            ParseRequestError::InvalidValue { parameter, value } => {
+                write!(f, "invalid value `{}` for parameter `{}`", value, parameter)
+            }
+            ParseRequestError::UnknownTrafficInfo(name) => {
+                write!(f, "unknown traffic info `{}`", name)
+            }
+        }
+    }
+}
+
+impl Error for ParseRequestError {}
+
+/// Errors of `MonitorRequest::validate` and the request builders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestValidationError {
+    /// None of the parameters is set although the API requires one of them.
+    MissingParameter(&'static str),
+    /// A value was given more than once for a parameter.
+    DuplicateValue {
+        parameter: &'static str,
+        value: String,
+    },
+    /// More values than the API accepts in one request.
+    TooManyValues {
+        parameter: &'static str,
+        count: usize,
+        max: usize,
+    },
+    InvalidValue {
+        parameter: &'static str,
+        value: String,
+    },
+}
+
+impl Display for RequestValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestValidationError::MissingParameter(parameter) => {
+                write!(f, "missing parameter `{}`", parameter)
+            }
+            RequestValidationError::DuplicateValue { parameter, value } => {
+                write!(
+                    f,
+                    "duplicate value `{}` for parameter `{}`",
+                    value, parameter
+                )
+            }
+            RequestValidationError::TooManyValues {
+                parameter,
+                count,
+                max,
+            } => write!(
+                f,
+                "{} values for parameter `{}`, at most {} are allowed",
+                count, parameter, max
+            ),
+            RequestValidationError::InvalidValue { parameter, value } => {
+                write!(f, "invalid value `{}` for parameter `{}`", value, parameter)
+            }
+        }
+    }
+}
+
+impl Error for RequestValidationError {}
+
+/// Errors loading requests with `RequestConfig`.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The configuration file could not be read.
+    Io(std::io::Error),
+    /// The configuration file cannot be parsed or does not describe request sets.
+    Parse(Box<dyn Error + Send + Sync>),
+    /// No request set with the name exists in the configuration.
+    UnknownSet(String),
+    /// A request of the set fails `validate`.
+    InvalidRequest {
+        set: String,
+        source: RequestValidationError,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "failed to read configuration: {}", err),
+            ConfigError::Parse(err) => write!(f, "failed to parse configuration: {}", err),
+            ConfigError::UnknownSet(name) => write!(f, "unknown request set `{}`", name),
+            ConfigError::InvalidRequest { set, source } => {
+                write!(f, "invalid request in set `{}`: {}", set, source)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io(err) => Some(err),
+            ConfigError::Parse(err) => Some(err.as_ref()),
+            ConfigError::InvalidRequest { source, .. } => Some(source),
+            ConfigError::UnknownSet(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(err: std::io::Error) -> Self {
+        ConfigError::Io(err)
+    }
+}
@@ -1,3 +1,28 @@
+#[cfg(feature = "client")]
+pub mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "client")]
+pub mod cache;
+#[cfg(feature = "client")]
+pub mod chunk;
+#[cfg(feature = "client")]
+pub mod circuit_breaker;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod config;
+pub mod error;
 pub mod helpers;
+pub mod ids;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
 pub mod models;
+#[cfg(feature = "client")]
+pub mod rate_limit;
+#[cfg(feature = "client")]
+pub mod retry;
 mod serde_deserializers;
+#[cfg(feature = "client")]
+mod single_flight;
+#[cfg(feature = "client")]
+pub mod transport;
@@ -0,0 +1,306 @@
+//! Local stand-in for the WL API to test code using this crate end to end without network.
+//!
+//! ```no_run
+//! # async fn example() {
+//! use wl_realtime_ogd::{cache::Endpoint, client::WlClient, mock_server::{MockReply, MockWlServer}};
+//! use wl_realtime_ogd::models::{MessageCode, MonitorRequest};
+//!
+//! let server = MockWlServer::start().await.unwrap();
+//! server.enqueue(Endpoint::Monitor, MockReply::message_code(MessageCode::DbOffline));
+//! let client = WlClient::builder().base_url(server.base_url()).build().unwrap();
+//! assert!(client.monitor(&MonitorRequest::new()).await.is_err());
+//! # }
+//! ```
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::Local;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+use crate::{cache::Endpoint, models::MessageCode};
+
+const MONITOR_FIXTURE: &str = include_str!("../tests/assets/monitor-response.json");
+const TRAFFIC_INFO_LIST_FIXTURE: &str = include_str!("../tests/assets/traffic-info-list.json");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Body {
+    Fixture,
+    Text(String),
+}
+
+/// Response of the mock server to a single request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockReply {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Body,
+    delay: Duration,
+}
+
+impl MockReply {
+    /// The fixture configured for the endpoint.
+    pub fn fixture() -> Self {
+        MockReply {
+            status: 200,
+            headers: vec![],
+            body: Body::Fixture,
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn json(body: impl Into<String>) -> Self {
+        MockReply {
+            body: Body::Text(body.into()),
+            ..MockReply::fixture()
+        }
+    }
+
+    /// An error response of the API with the given code and no data, the fixture for `OK`.
+    pub fn message_code(code: MessageCode) -> Self {
+        if code.is_success() {
+            return MockReply::fixture();
+        }
+        MockReply::json(format!(
+            r#"{{"data":{{}},"message":{{"value":"{}","messageCode":{},"serverTime":"{}"}}}}"#,
+            code,
+            code.code(),
+            Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%z")
+        ))
+    }
+
+    /// An HTTP error status with an empty body, e.g. 429 or 503.
+    pub fn status(status: u16) -> Self {
+        MockReply {
+            status,
+            body: Body::Text(String::new()),
+            ..MockReply::fixture()
+        }
+    }
+
+    /// A body that is not valid JSON.
+    pub fn malformed() -> Self {
+        MockReply::json(r#"{"data":{"monitors":[{"locationStop":"#)
+    }
+
+    /// Replaces the body, e.g. to add an error page to `MockReply::status`.
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = Body::Text(body.into());
+        self
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Waits before answering, in addition to the latency of the server.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    fixtures: HashMap<Endpoint, String>,
+    defaults: HashMap<Endpoint, MockReply>,
+    queues: HashMap<Endpoint, VecDeque<MockReply>>,
+    latency: Duration,
+    requests: Vec<String>,
+}
+
+impl State {
+    fn reply(&mut self, endpoint: Endpoint) -> (u16, Vec<(String, String)>, String, Duration) {
+        let reply = match self.queues.get_mut(&endpoint).and_then(VecDeque::pop_front) {
+            Some(reply) => reply,
+            None => self
+                .defaults
+                .get(&endpoint)
+                .cloned()
+                .unwrap_or_else(MockReply::fixture),
+        };
+        let body = match reply.body {
+            Body::Fixture => self.fixtures[&endpoint].clone(),
+            Body::Text(body) => body,
+        };
+        (
+            reply.status,
+            reply.headers,
+            body,
+            self.latency + reply.delay,
+        )
+    }
+}
+
+/// HTTP server emulating `/monitor` and `/trafficInfoList` of the WL API.
+///
+/// Every request is answered with the next enqueued reply of its endpoint, or the
+/// endpoint's default reply (its fixture unless changed) once the queue is empty.
+/// Other paths are answered with 404. The server stops when it is dropped.
+#[derive(Debug)]
+pub struct MockWlServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    task: JoinHandle<()>,
+}
+
+impl MockWlServer {
+    /// Starts the server on a random local port, must be called inside a tokio runtime.
+    pub async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State {
+            fixtures: HashMap::from([
+                (Endpoint::Monitor, String::from(MONITOR_FIXTURE)),
+                (
+                    Endpoint::TrafficInfoList,
+                    String::from(TRAFFIC_INFO_LIST_FIXTURE),
+                ),
+            ]),
+            defaults: HashMap::new(),
+            queues: HashMap::new(),
+            latency: Duration::ZERO,
+            requests: vec![],
+        }));
+
+        let shared = state.clone();
+        let task = tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                tokio::spawn(handle(socket, shared.clone()));
+            }
+        });
+
+        Ok(MockWlServer { addr, state, task })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Base URL to configure a client with.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Replaces the body served by `MockReply::fixture()` for an endpoint.
+    pub fn set_fixture(&self, endpoint: Endpoint, body: impl Into<String>) {
+        let mut state = self.state.lock().unwrap();
+        state.fixtures.insert(endpoint, body.into());
+    }
+
+    /// Reply used for an endpoint once its queue is empty.
+    pub fn set_default(&self, endpoint: Endpoint, reply: MockReply) {
+        let mut state = self.state.lock().unwrap();
+        state.defaults.insert(endpoint, reply);
+    }
+
+    /// Reply used for the next request to an endpoint.
+    pub fn enqueue(&self, endpoint: Endpoint, reply: MockReply) {
+        let mut state = self.state.lock().unwrap();
+        state.queues.entry(endpoint).or_default().push_back(reply);
+    }
+
+    /// Answers the next requests to an endpoint with `replies` in order and repeats the
+    /// last one afterwards. Replaces replies enqueued before.
+    pub fn set_replies(&self, endpoint: Endpoint, replies: impl IntoIterator<Item = MockReply>) {
+        let mut replies: VecDeque<MockReply> = replies.into_iter().collect();
+        let mut state = self.state.lock().unwrap();
+        match replies.pop_back() {
+            Some(last) => state.defaults.insert(endpoint, last),
+            None => state.defaults.remove(&endpoint),
+        };
+        state.queues.insert(endpoint, replies);
+    }
+
+    /// Delay before every reply.
+    pub fn set_latency(&self, latency: Duration) {
+        self.state.lock().unwrap().latency = latency;
+    }
+
+    /// Paths including the query (`/monitor?stopId=1`) of the requests received so far.
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockWlServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle(mut socket: TcpStream, state: Arc<Mutex<State>>) {
+    let mut buffer = vec![0u8; 8192];
+    let mut read = 0;
+    while !buffer[..read]
+        .windows(4)
+        .any(|window| window == b"\r\n\r\n")
+    {
+        if read == buffer.len() {
+            return;
+        }
+        match socket.read(&mut buffer[read..]).await {
+            Ok(0) | Err(_) => return,
+            Ok(n) => read += n,
+        }
+    }
+    let head = String::from_utf8_lossy(&buffer[..read]);
+    let target = head
+        .lines()
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .unwrap_or_default()
+        .to_owned();
+
+    let endpoint = match target.split('?').next().unwrap_or_default() {
+        path if path.ends_with("/monitor") => Some(Endpoint::Monitor),
+        path if path.ends_with("/trafficInfoList") => Some(Endpoint::TrafficInfoList),
+        _ => None,
+    };
+    let (status, headers, body, delay) = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(target);
+        match endpoint {
+            Some(endpoint) => state.reply(endpoint),
+            None => (404, vec![], String::new(), state.latency),
+        }
+    };
+
+    tokio::time::sleep(delay).await;
+    let mut response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        status,
+        reason(status),
+        body.len()
+    );
+    for (name, value) in headers {
+        response.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    response.push_str("\r\n");
+    response.push_str(&body);
+    let _ = socket.write_all(response.as_bytes()).await;
+    let _ = socket.shutdown().await;
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Mock",
+    }
+}
@@ -1,23 +1,46 @@
 use chrono::{DateTime, FixedOffset};
-use serde::{Deserialize, Deserializer, Serialize};
-use std::{error::Error, fmt::Display};
+use indexmap::IndexMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt::Display, str::FromStr};
+use url::{form_urlencoded, Url};
 
+#[cfg(feature = "client")]
+use crate::client::default_client;
 use crate::{
-    helpers::join_vec,
-    serde_deserializers::{date_format, optional_date_format},
+    error::{ParseRequestError, RequestValidationError, WlError},
+    ids::{Diva, LineName, Rbl, TrafficInfoName},
+    serde_deserializers::{date_format, display_string, null_as_default, optional_date_format},
 };
 
-const WL_ENDPOINT: &str = "https://www.wienerlinien.at/ogd_realtime";
+/// Maximum number of `stopId` parameters validated by `MonitorRequest::validate`.
+pub const MAX_STOP_IDS: usize = 50;
 
 pub trait BuildRequestUrl {
+    /// Path and form-urlencoded query of the request, e.g. `/monitor?stopId=123`.
     fn build_request_url(&self) -> String;
+
+    /// Absolute URL of the request for an API base URL such as `WL_ENDPOINT`.
+    fn request_url(&self, base_url: &str) -> Result<Url, url::ParseError> {
+        Url::parse(&format!(
+            "{}{}",
+            base_url.trim_end_matches('/'),
+            self.build_request_url()
+        ))
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Parameters of a `/monitor` request, (de)serialized with the API's parameter names
+/// so it can be kept in configuration files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MonitorRequest {
-    pub stop_id: Vec<u32>,
-    pub diva: Option<u32>,
+    #[serde(rename = "stopId")]
+    pub stop_id: Vec<Rbl>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diva: Option<Diva>,
+    #[serde(rename = "activateTrafficInfo")]
     pub activate_traffic_info: Vec<ExtTrafficInfoEnum>,
+    #[serde(rename = "aArea")]
     pub a_area: bool,
 }
 
@@ -31,10 +54,38 @@ impl MonitorRequest {
         }
     }
 
-    pub async fn run(&self) -> Result<MonitorResponse, Box<dyn Error>> {
-        let url = WL_ENDPOINT.to_owned() + &self.build_request_url();
-        let response = reqwest::get(url).await?.json::<MonitorResponse>().await?;
-        Ok(response)
+    pub fn builder() -> MonitorRequestBuilder {
+        MonitorRequestBuilder::new()
+    }
+
+    /// Checks the request locally for errors the API would reject it for: neither
+    /// `stop_id` nor `diva` set, more than `MAX_STOP_IDS` stops and duplicate values.
+    pub fn validate(&self) -> Result<(), RequestValidationError> {
+        if self.stop_id.is_empty() && self.diva.is_none() {
+            return Err(RequestValidationError::MissingParameter("stopId or diva"));
+        }
+        if self.stop_id.len() > MAX_STOP_IDS {
+            return Err(RequestValidationError::TooManyValues {
+                parameter: "stopId",
+                count: self.stop_id.len(),
+                max: MAX_STOP_IDS,
+            });
+        }
+        check_unique("stopId", &self.stop_id)?;
+        check_unique("activateTrafficInfo", &self.activate_traffic_info)
+    }
+
+    /// Sends the request with a default `WlClient` shared by the `run()` calls on the
+    /// current tokio runtime, see `client::set_default_client`. Programs that poll
+    /// repeatedly should build and keep their own `WlClient`.
+    #[cfg(feature = "client")]
+    pub async fn run(&self) -> Result<MonitorResponse, WlError> {
+        default_client()?.monitor(self).await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn run_blocking(&self) -> Result<MonitorResponse, WlError> {
+        crate::blocking::default_client()?.monitor(self)
     }
 }
 
@@ -44,34 +95,128 @@ impl Default for MonitorRequest {
     }
 }
 
+/// Serializer appending form-urlencoded parameters to `path?`.
+fn query_serializer(path: &str) -> form_urlencoded::Serializer<'static, String> {
+    form_urlencoded::Serializer::for_suffix(format!("{}?", path), path.len() + 1)
+}
+
 impl BuildRequestUrl for MonitorRequest {
     fn build_request_url(&self) -> String {
-        let mut url = String::from("/monitor?");
-        let mut url_segments: Vec<String> = vec![];
-        if !self.stop_id.is_empty() {
-            let mut stops = join_vec("stopId=", &self.stop_id);
-            url_segments.append(&mut stops);
+        let mut query = query_serializer("/monitor");
+        for stop_id in &self.stop_id {
+            query.append_pair("stopId", &stop_id.to_string());
         }
-        if self.diva.is_some() {
-            url_segments.push(format!("diva={}", self.diva.unwrap()));
+        if let Some(diva) = self.diva {
+            query.append_pair("diva", &diva.to_string());
         }
-        if !self.activate_traffic_info.is_empty() {
-            let mut traffic_info = join_vec("activateTrafficInfo=", &self.activate_traffic_info);
-            url_segments.append(&mut traffic_info);
+        for traffic_info in &self.activate_traffic_info {
+            query.append_pair("activateTrafficInfo", &traffic_info.to_string());
         }
         if self.a_area {
-            url_segments.push(String::from("aArea=1"));
+            query.append_pair("aArea", "1");
         }
+        query.finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MonitorRequestBuilder {
+    request: MonitorRequest,
+}
+
+impl MonitorRequestBuilder {
+    pub fn new() -> Self {
+        MonitorRequestBuilder {
+            request: MonitorRequest::new(),
+        }
+    }
+
+    pub fn stop_id(mut self, stop_id: impl Into<Rbl>) -> Self {
+        self.request.stop_id.push(stop_id.into());
+        self
+    }
+
+    pub fn stop_ids<T: Into<Rbl>>(mut self, stop_ids: impl IntoIterator<Item = T>) -> Self {
+        self.request
+            .stop_id
+            .extend(stop_ids.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn diva(mut self, diva: impl Into<Diva>) -> Self {
+        self.request.diva = Some(diva.into());
+        self
+    }
 
-        url.push_str(&url_segments.join("&"));
-        url
+    pub fn activate_traffic_info(mut self, traffic_info: ExtTrafficInfoEnum) -> Self {
+        self.request.activate_traffic_info.push(traffic_info);
+        self
+    }
+
+    pub fn a_area(mut self, a_area: bool) -> Self {
+        self.request.a_area = a_area;
+        self
+    }
+
+    pub fn build(self) -> Result<MonitorRequest, RequestValidationError> {
+        self.request.validate()?;
+        Ok(self.request)
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl TryFrom<&Url> for MonitorRequest {
+    type Error = ParseRequestError;
+
+    fn try_from(url: &Url) -> Result<Self, Self::Error> {
+        let mut request = MonitorRequest::new();
+        for (parameter, value) in query_pairs(url, "monitor")? {
+            match parameter.as_ref() {
+                "stopId" => request.stop_id.push(parse_value(&parameter, &value)?),
+                "diva" => {
+                    if request
+                        .diva
+                        .replace(parse_value(&parameter, &value)?)
+                        .is_some()
+                    {
+                        return Err(ParseRequestError::DuplicateParameter(
+                            parameter.into_owned(),
+                        ));
+                    }
+                }
+                "activateTrafficInfo" => request
+                    .activate_traffic_info
+                    .push(parse_value(&parameter, &value)?),
+                "aArea" => {
+                    request.a_area = match value.as_ref() {
+                        "1" => true,
+                        "0" => false,
+                        _ => return Err(invalid_value(&parameter, &value)),
+                    }
+                }
+                _ => return Err(ParseRequestError::UnknownParameter(parameter.into_owned())),
+            }
+        }
+        Ok(request)
+    }
+}
+
+impl FromStr for MonitorRequest {
+    type Err = ParseRequestError;
+
+    /// Parses a path such as `/monitor?stopId=123` or an absolute URL.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MonitorRequest::try_from(&parse_request_url(s)?)
+    }
+}
+
+/// Parameters of a `/trafficInfoList` request, (de)serialized like `MonitorRequest`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TrafficInfoListRequest {
-    pub related_line: Vec<String>,
-    pub related_stop: Vec<u32>,
+    #[serde(rename = "relatedLine")]
+    pub related_line: Vec<LineName>,
+    #[serde(rename = "relatedStop")]
+    pub related_stop: Vec<Rbl>,
     pub name: Vec<TrafficInfoEnum>,
 }
 
@@ -84,13 +229,38 @@ impl TrafficInfoListRequest {
         }
     }
 
-    pub async fn run(&self) -> Result<TrafficInfoListResponse, Box<dyn Error>> {
-        let url = WL_ENDPOINT.to_owned() + &self.build_request_url();
-        let response = reqwest::get(url)
-            .await?
-            .json::<TrafficInfoListResponse>()
-            .await?;
-        Ok(response)
+    pub fn builder() -> TrafficInfoListRequestBuilder {
+        TrafficInfoListRequestBuilder::new()
+    }
+
+    /// Checks the request locally for empty line names and duplicate values.
+    pub fn validate(&self) -> Result<(), RequestValidationError> {
+        if self
+            .related_line
+            .iter()
+            .any(|line| line.as_str().trim().is_empty())
+        {
+            return Err(RequestValidationError::InvalidValue {
+                parameter: "relatedLine",
+                value: String::new(),
+            });
+        }
+        check_unique("relatedLine", &self.related_line)?;
+        check_unique("relatedStop", &self.related_stop)?;
+        check_unique("name", &self.name)
+    }
+
+    /// Sends the request with a default `WlClient` shared by the `run()` calls on the
+    /// current tokio runtime, see `client::set_default_client`. Programs that poll
+    /// repeatedly should build and keep their own `WlClient`.
+    #[cfg(feature = "client")]
+    pub async fn run(&self) -> Result<TrafficInfoListResponse, WlError> {
+        default_client()?.traffic_info_list(self).await
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn run_blocking(&self) -> Result<TrafficInfoListResponse, WlError> {
+        crate::blocking::default_client()?.traffic_info_list(self)
     }
 }
 
@@ -102,27 +272,134 @@ impl Default for TrafficInfoListRequest {
 
 impl BuildRequestUrl for TrafficInfoListRequest {
     fn build_request_url(&self) -> String {
-        let mut url = String::from("/trafficInfoList?");
-        let mut url_segments: Vec<String> = vec![];
-        if !self.related_line.is_empty() {
-            let mut lines = join_vec("relatedLine=", &self.related_line);
-            url_segments.append(&mut lines);
+        let mut query = query_serializer("/trafficInfoList");
+        for line in &self.related_line {
+            query.append_pair("relatedLine", line.as_str());
         }
-        if !self.related_stop.is_empty() {
-            let mut stops = join_vec("relatedStop=", &self.related_stop);
-            url_segments.append(&mut stops);
+        for stop in &self.related_stop {
+            query.append_pair("relatedStop", &stop.to_string());
         }
-        if !self.name.is_empty() {
-            let mut names = join_vec("name=", &self.name);
-            url_segments.append(&mut names);
+        for name in &self.name {
+            query.append_pair("name", &name.to_string());
         }
+        query.finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TrafficInfoListRequestBuilder {
+    request: TrafficInfoListRequest,
+}
 
-        url.push_str(&url_segments.join("&"));
-        url
+impl TrafficInfoListRequestBuilder {
+    pub fn new() -> Self {
+        TrafficInfoListRequestBuilder {
+            request: TrafficInfoListRequest::new(),
+        }
+    }
+
+    pub fn related_line(mut self, line: impl Into<LineName>) -> Self {
+        self.request.related_line.push(line.into());
+        self
+    }
+
+    pub fn related_stop(mut self, stop: impl Into<Rbl>) -> Self {
+        self.request.related_stop.push(stop.into());
+        self
+    }
+
+    pub fn name(mut self, name: TrafficInfoEnum) -> Self {
+        self.request.name.push(name);
+        self
+    }
+
+    pub fn build(self) -> Result<TrafficInfoListRequest, RequestValidationError> {
+        self.request.validate()?;
+        Ok(self.request)
+    }
+}
+
+impl TryFrom<&Url> for TrafficInfoListRequest {
+    type Error = ParseRequestError;
+
+    fn try_from(url: &Url) -> Result<Self, Self::Error> {
+        let mut request = TrafficInfoListRequest::new();
+        for (parameter, value) in query_pairs(url, "trafficInfoList")? {
+            match parameter.as_ref() {
+                "relatedLine" => request
+                    .related_line
+                    .push(LineName::from(value.into_owned())),
+                "relatedStop" => request.related_stop.push(parse_value(&parameter, &value)?),
+                "name" => request.name.push(parse_value(&parameter, &value)?),
+                _ => return Err(ParseRequestError::UnknownParameter(parameter.into_owned())),
+            }
+        }
+        Ok(request)
+    }
+}
+
+impl FromStr for TrafficInfoListRequest {
+    type Err = ParseRequestError;
+
+    /// Parses a path such as `/trafficInfoList?relatedLine=U1` or an absolute URL.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TrafficInfoListRequest::try_from(&parse_request_url(s)?)
+    }
+}
+
+fn check_unique<T: PartialEq + Display>(
+    parameter: &'static str,
+    values: &[T],
+) -> Result<(), RequestValidationError> {
+    for (index, value) in values.iter().enumerate() {
+        if values[..index].contains(value) {
+            return Err(RequestValidationError::DuplicateValue {
+                parameter,
+                value: value.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Parses an absolute URL or a path relative to the API base URL.
+fn parse_request_url(s: &str) -> Result<Url, ParseRequestError> {
+    let url = match Url::parse(s) {
+        Err(url::ParseError::RelativeUrlWithoutBase) => {
+            Url::parse("http://localhost/").and_then(|base| base.join(s))
+        }
+        url => url,
+    };
+    url.map_err(|err| ParseRequestError::InvalidUrl(err.to_string()))
+}
+
+/// Query of `url` if the last segment of its path is `endpoint`.
+fn query_pairs<'a>(
+    url: &'a Url,
+    endpoint: &str,
+) -> Result<form_urlencoded::Parse<'a>, ParseRequestError> {
+    if url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        != Some(endpoint)
+    {
+        return Err(ParseRequestError::UnexpectedPath(url.path().to_owned()));
+    }
+    Ok(url.query_pairs())
+}
+
+fn parse_value<T: FromStr>(parameter: &str, value: &str) -> Result<T, ParseRequestError> {
+    value.parse().map_err(|_| invalid_value(parameter, value))
+}
+
+fn invalid_value(parameter: &str, value: &str) -> ParseRequestError {
+    ParseRequestError::InvalidValue {
+        parameter: parameter.to_owned(),
+        value: value.to_owned(),
     }
 }
 
-#[derive(Serialize, Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TrafficInfoEnum {
     StoerungLang,
     StoerungKurz,
@@ -142,7 +419,41 @@ impl Display for TrafficInfoEnum {
     }
 }
 
-#[derive(Serialize, Debug, Clone, PartialEq)]
+impl FromStr for TrafficInfoEnum {
+    type Err = ParseRequestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stoerunglang" => Ok(TrafficInfoEnum::StoerungLang),
+            "stoerungkurz" => Ok(TrafficInfoEnum::StoerungKurz),
+            "aufzugsinfo" => Ok(TrafficInfoEnum::AufzugsInfo),
+            "fahrtreppeninfo" => Ok(TrafficInfoEnum::FahrtreppenInfo),
+            other => Err(ParseRequestError::UnknownTrafficInfo(other.to_owned())),
+        }
+    }
+}
+
+/// Serialized by its API name, e.g. `stoerunglang`.
+impl Serialize for TrafficInfoEnum {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TrafficInfoEnum {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum ExtTrafficInfoEnum {
     TrafficInfo(TrafficInfoEnum),
     Information,
@@ -157,15 +468,120 @@ impl Display for ExtTrafficInfoEnum {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl FromStr for ExtTrafficInfoEnum {
+    type Err = ParseRequestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "information" => Ok(ExtTrafficInfoEnum::Information),
+            other => other.parse().map(ExtTrafficInfoEnum::TrafficInfo),
+        }
+    }
+}
+
+/// Serialized by its API name, e.g. `information`.
+impl Serialize for ExtTrafficInfoEnum {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtTrafficInfoEnum {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MessageCode {
-    OK = 1,
-    DbOffline = 311,
-    StopDoesNotExist = 312,
-    RequestLimitExceeded = 316,
-    GetParamInvalid = 320,
-    GetParamMissing = 321,
-    NoDataFound = 322,
+    OK,
+    DbOffline,
+    StopDoesNotExist,
+    RequestLimitExceeded,
+    GetParamInvalid,
+    GetParamMissing,
+    NoDataFound,
+    /// A code not (yet) known to this crate.
+    Unknown(u32),
+}
+
+impl MessageCode {
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => MessageCode::OK,
+            311 => MessageCode::DbOffline,
+            312 => MessageCode::StopDoesNotExist,
+            316 => MessageCode::RequestLimitExceeded,
+            320 => MessageCode::GetParamInvalid,
+            321 => MessageCode::GetParamMissing,
+            322 => MessageCode::NoDataFound,
+            other => MessageCode::Unknown(other),
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            MessageCode::OK => 1,
+            MessageCode::DbOffline => 311,
+            MessageCode::StopDoesNotExist => 312,
+            MessageCode::RequestLimitExceeded => 316,
+            MessageCode::GetParamInvalid => 320,
+            MessageCode::GetParamMissing => 321,
+            MessageCode::NoDataFound => 322,
+            MessageCode::Unknown(code) => *code,
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        *self == MessageCode::OK
+    }
+
+    /// Whether the same request may succeed when sent again later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            MessageCode::DbOffline | MessageCode::RequestLimitExceeded
+        )
+    }
+}
+
+impl From<u32> for MessageCode {
+    fn from(code: u32) -> Self {
+        MessageCode::from_code(code)
+    }
+}
+
+impl From<MessageCode> for u32 {
+    fn from(code: MessageCode) -> Self {
+        code.code()
+    }
+}
+
+impl Display for MessageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str: &str = match self {
+            MessageCode::OK => "OK",
+            MessageCode::DbOffline => "Datenbank offline (database offline)",
+            MessageCode::StopDoesNotExist => "Haltepunkt existiert nicht (stop does not exist)",
+            MessageCode::RequestLimitExceeded => {
+                "Maximale Anfrageanzahl überschritten (request limit exceeded)"
+            }
+            MessageCode::GetParamInvalid => "GET-Parameter ungültig (invalid GET parameter)",
+            MessageCode::GetParamMissing => "GET-Parameter fehlt (missing GET parameter)",
+            MessageCode::NoDataFound => {
+                "Keine Daten in der Datenbank gefunden (no data found in database)"
+            }
+            MessageCode::Unknown(_) => "Unbekannter Code (unknown code)",
+        };
+        write!(f, "{} {}", self.code(), str)
+    }
 }
 
 impl<'de> Deserialize<'de> for MessageCode {
@@ -174,24 +590,20 @@ impl<'de> Deserialize<'de> for MessageCode {
         D: Deserializer<'de>,
     {
         let code: u32 = Deserialize::deserialize(deserializer)?;
+        Ok(MessageCode::from_code(code))
+    }
+}
 
-        match code {
-            1 => Ok(MessageCode::OK),
-            311 => Ok(MessageCode::DbOffline),
-            312 => Ok(MessageCode::StopDoesNotExist),
-            316 => Ok(MessageCode::RequestLimitExceeded),
-            320 => Ok(MessageCode::GetParamInvalid),
-            321 => Ok(MessageCode::GetParamMissing),
-            322 => Ok(MessageCode::NoDataFound),
-            _ => Err(serde::de::Error::custom(format!(
-                "Unknown message code: {}",
-                code
-            ))),
-        }
+impl Serialize for MessageCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u32(self.code())
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Message {
     pub value: String,
     #[serde(rename = "messageCode")]
@@ -200,26 +612,45 @@ pub struct Message {
     pub server_time: DateTime<FixedOffset>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+impl Message {
+    pub fn is_ok(&self) -> bool {
+        self.message_code.is_success()
+    }
+
+    pub(crate) fn into_result(self) -> Result<Self, WlError> {
+        if self.is_ok() {
+            Ok(self)
+        } else {
+            Err(WlError::Api {
+                code: self.message_code,
+                message: self.value,
+            })
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Response {
     pub message: Message,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Geometry {
     #[serde(rename = "type")]
     pub geometry_type: String,
     pub coordinates: Vec<f64>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Attributes {
-    pub rbl: i32,
+    pub rbl: Rbl,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Properties {
-    pub name: String,
+    /// DIVA number of the station, a string on the wire.
+    #[serde(serialize_with = "display_string::serialize")]
+    pub name: Diva,
     pub title: String,
     pub municipality: String,
     #[serde(rename = "municipalityId")]
@@ -231,7 +662,7 @@ pub struct Properties {
     pub attributes: Attributes,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct LocationStop {
     #[serde(rename = "type")]
     pub location_type: String,
@@ -239,18 +670,23 @@ pub struct LocationStop {
     pub properties: Properties,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct DepartureTime {
     #[serde(rename = "timePlanned", with = "date_format")]
     pub time_planned: DateTime<FixedOffset>,
-    #[serde(rename = "timeReal", default, with = "optional_date_format")]
+    #[serde(
+        rename = "timeReal",
+        default,
+        with = "optional_date_format",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub time_real: Option<DateTime<FixedOffset>>,
     pub countdown: i32,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Vehicle {
-    pub name: String,
+    pub name: LineName,
     pub towards: String,
     pub direction: String,
     #[serde(rename = "richtungsId")]
@@ -264,21 +700,22 @@ pub struct Vehicle {
     pub vehicle_type: String,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Departure {
     #[serde(rename = "departureTime")]
     pub departure_time: DepartureTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub vehicle: Option<Vehicle>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Departures {
     pub departure: Vec<Departure>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Line {
-    pub name: String,
+    pub name: LineName,
     pub towards: String,
     pub direction: String,
     #[serde(rename = "richtungsId")]
@@ -291,47 +728,79 @@ pub struct Line {
     pub departures: Departures,
     #[serde(rename = "type")]
     pub line_type: String,
-    #[serde(rename = "lineId")]
+    #[serde(rename = "lineId", skip_serializing_if = "Option::is_none")]
     pub line_id: Option<i32>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Monitor {
     #[serde(rename = "locationStop")]
     pub location_stop: LocationStop,
     pub lines: Vec<Line>,
-    #[serde(rename = "refTrafficInfoNames")]
-    pub ref_traffic_info_names: Option<Vec<String>>,
+    #[serde(
+        rename = "refTrafficInfoNames",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ref_traffic_info_names: Option<Vec<TrafficInfoName>>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Time {
-    #[serde(default, with = "optional_date_format")]
+    #[serde(
+        default,
+        with = "optional_date_format",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub start: Option<DateTime<FixedOffset>>,
-    #[serde(default, with = "optional_date_format")]
+    #[serde(
+        default,
+        with = "optional_date_format",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub end: Option<DateTime<FixedOffset>>,
-    #[serde(default, with = "optional_date_format")]
+    #[serde(
+        default,
+        with = "optional_date_format",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub resume: Option<DateTime<FixedOffset>>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+/// Fields are declared in the order the API sends them.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AttributesTrafficInfo {
-    pub status: Option<String>,
+    /// End of the outage as sent by the API.
+    #[serde(rename = "ausBis", skip_serializing_if = "Option::is_none")]
+    pub aus_bis: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(rename = "relatedLines", skip_serializing_if = "Option::is_none")]
+    pub related_lines: Option<Vec<LineName>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub station: Option<String>,
+    /// Start of the outage as sent by the API, e.g. `18.07.2023 01:15`.
+    #[serde(rename = "ausVon", skip_serializing_if = "Option::is_none")]
+    pub aus_von: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<String>,
-    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub towards: Option<String>,
-    #[serde(rename = "relatedLines")]
-    pub related_lines: Option<Vec<String>>,
-    #[serde(rename = "relatedStops")]
-    pub related_stops: Option<Vec<u32>>,
+    #[serde(rename = "relatedStops", skip_serializing_if = "Option::is_none")]
+    pub related_stops: Option<Vec<Rbl>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Vehicle type by line name, e.g. `ptMetro` for `U4`, in the order of the API.
+    #[serde(rename = "relatedLineTypes", skip_serializing_if = "Option::is_none")]
+    pub related_line_types: Option<IndexMap<String, String>>,
 }
 
+/// Infos with a `priority` or `owner` come from the disruption feed of the API, which
+/// sends `time` before `attributes`; `Serialize` keeps that order.
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TrafficInfo {
     #[serde(rename = "refTrafficInfoCategoryId")]
     pub ref_traffic_info_category_id: i32,
-    pub name: String,
+    pub name: TrafficInfoName,
     pub priority: Option<String>,
     pub owner: Option<String>,
     pub title: String,
@@ -339,12 +808,53 @@ pub struct TrafficInfo {
     pub time: Option<Time>,
     pub attributes: Option<AttributesTrafficInfo>,
     #[serde(rename = "relatedLines")]
-    pub related_lines: Option<Vec<String>>,
+    pub related_lines: Option<Vec<LineName>>,
     #[serde(rename = "relatedStops")]
-    pub related_stops: Option<Vec<i32>>,
+    pub related_stops: Option<Vec<Rbl>>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+impl Serialize for TrafficInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        fn skip_if_none<S: SerializeStruct, T: Serialize>(
+            state: &mut S,
+            key: &'static str,
+            value: &Option<T>,
+        ) -> Result<(), S::Error> {
+            match value {
+                Some(value) => state.serialize_field(key, value),
+                None => state.skip_field(key),
+            }
+        }
+
+        let mut state = serializer.serialize_struct("TrafficInfo", 10)?;
+        state.serialize_field(
+            "refTrafficInfoCategoryId",
+            &self.ref_traffic_info_category_id,
+        )?;
+        state.serialize_field("name", &self.name)?;
+        skip_if_none(&mut state, "priority", &self.priority)?;
+        skip_if_none(&mut state, "owner", &self.owner)?;
+        state.serialize_field("title", &self.title)?;
+        state.serialize_field("description", &self.description)?;
+        if self.priority.is_some() || self.owner.is_some() {
+            skip_if_none(&mut state, "time", &self.time)?;
+            skip_if_none(&mut state, "attributes", &self.attributes)?;
+        } else {
+            skip_if_none(&mut state, "attributes", &self.attributes)?;
+            skip_if_none(&mut state, "time", &self.time)?;
+        }
+        skip_if_none(&mut state, "relatedLines", &self.related_lines)?;
+        skip_if_none(&mut state, "relatedStops", &self.related_stops)?;
+        state.end()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct TrafficInfoCategory {
     pub id: i32,
     #[serde(rename = "refTrafficInfoCategoryGroupId")]
@@ -355,41 +865,111 @@ pub struct TrafficInfoCategory {
     pub title: String,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct TrafficInfoCategoryGroup {
     pub id: i32,
     pub name: String,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct MonitorResponseData {
+    #[serde(default)]
     pub monitors: Vec<Monitor>,
-    #[serde(rename = "trafficInfos")]
+    #[serde(rename = "trafficInfos", skip_serializing_if = "Option::is_none")]
     pub traffic_infos: Option<Vec<TrafficInfo>>,
-    #[serde(rename = "trafficInfoCategories")]
+    #[serde(
+        rename = "trafficInfoCategories",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub traffic_info_categories: Option<Vec<TrafficInfoCategory>>,
-    #[serde(rename = "trafficInfoCategoryGroups")]
+    #[serde(
+        rename = "trafficInfoCategoryGroups",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub traffic_info_category_groups: Option<Vec<TrafficInfoCategoryGroup>>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+impl MonitorResponseData {
+    /// Appends the monitors of `other`, skipping traffic infos, categories and groups
+    /// already present by name or id.
+    pub fn merge(&mut self, other: MonitorResponseData) {
+        self.monitors.extend(other.monitors);
+        merge_unique(&mut self.traffic_infos, other.traffic_infos, |a, b| {
+            a.name == b.name
+        });
+        merge_unique(
+            &mut self.traffic_info_categories,
+            other.traffic_info_categories,
+            |a, b| a.id == b.id,
+        );
+        merge_unique(
+            &mut self.traffic_info_category_groups,
+            other.traffic_info_category_groups,
+            |a, b| a.id == b.id,
+        );
+    }
+}
+
+fn merge_unique<T>(
+    target: &mut Option<Vec<T>>,
+    other: Option<Vec<T>>,
+    eq: impl Fn(&T, &T) -> bool,
+) {
+    let Some(other) = other else {
+        return;
+    };
+    let target = target.get_or_insert_with(Vec::new);
+    for item in other {
+        if !target.iter().any(|existing| eq(existing, &item)) {
+            target.push(item);
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct MonitorResponse {
-    pub message: Message,
+    // error responses may omit `data` or send it empty
+    #[serde(default, with = "null_as_default")]
     pub data: MonitorResponseData,
+    pub message: Message,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+impl MonitorResponse {
+    /// Converts a response carrying a non-`OK` `MessageCode` into `WlError::Api`.
+    pub fn into_result(self) -> Result<Self, WlError> {
+        let message = self.message.into_result()?;
+        Ok(MonitorResponse { message, ..self })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
 pub struct TrafficInfoListResponseData {
-    #[serde(rename = "trafficInfos")]
+    #[serde(rename = "trafficInfos", skip_serializing_if = "Option::is_none")]
     pub traffic_infos: Option<Vec<TrafficInfo>>,
-    #[serde(rename = "trafficInfoCategories")]
+    #[serde(
+        rename = "trafficInfoCategories",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub traffic_info_categories: Option<Vec<TrafficInfoCategory>>,
-    #[serde(rename = "trafficInfoCategoryGroups")]
+    #[serde(
+        rename = "trafficInfoCategoryGroups",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub traffic_info_category_groups: Option<Vec<TrafficInfoCategoryGroup>>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct TrafficInfoListResponse {
-    pub message: Message,
+    // error responses may omit `data` or send it empty
+    #[serde(default, with = "null_as_default")]
     pub data: TrafficInfoListResponseData,
+    pub message: Message,
+}
+
+impl TrafficInfoListResponse {
+    /// Converts a response carrying a non-`OK` `MessageCode` into `WlError::Api`.
+    pub fn into_result(self) -> Result<Self, WlError> {
+        let message = self.message.into_result()?;
+        Ok(TrafficInfoListResponse { message, ..self })
+    }
 }
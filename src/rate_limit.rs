@@ -0,0 +1,145 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::error::WlError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Wait until a token becomes available.
+    Wait,
+    /// Return `WlError::RateLimited` immediately if no token is available.
+    FailFast,
+}
+
+/// Request budget of a `RateLimiter`. Without any budget configured, requests are not limited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    per_second: Option<u32>,
+    per_minute: Option<u32>,
+    mode: RateLimitMode,
+}
+
+impl RateLimit {
+    pub fn new() -> Self {
+        RateLimit {
+            per_second: None,
+            per_minute: None,
+            mode: RateLimitMode::Wait,
+        }
+    }
+
+    pub fn per_second(mut self, requests: u32) -> Self {
+        self.per_second = Some(requests.max(1));
+        self
+    }
+
+    pub fn per_minute(mut self, requests: u32) -> Self {
+        self.per_minute = Some(requests.max(1));
+        self
+    }
+
+    pub fn mode(mut self, mode: RateLimitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        RateLimit::new()
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests: u32, period: Duration) -> Self {
+        let capacity = f64::from(requests);
+        TokenBucket {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: capacity / period.as_secs_f64(),
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    fn time_until_available(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.refill_per_sec)
+        }
+    }
+}
+
+/// Token bucket limiter shared by all requests issued through a `WlClient` (and its clones).
+#[derive(Debug)]
+pub struct RateLimiter {
+    buckets: Mutex<Vec<TokenBucket>>,
+    mode: RateLimitMode,
+}
+
+impl RateLimiter {
+    pub fn new(limit: RateLimit) -> Self {
+        let mut buckets = vec![];
+        if let Some(requests) = limit.per_second {
+            buckets.push(TokenBucket::new(requests, Duration::from_secs(1)));
+        }
+        if let Some(requests) = limit.per_minute {
+            buckets.push(TokenBucket::new(requests, Duration::from_secs(60)));
+        }
+        RateLimiter {
+            buckets: Mutex::new(buckets),
+            mode: limit.mode,
+        }
+    }
+
+    /// Takes one token, waiting for it or failing depending on the configured mode.
+    pub async fn acquire(&self) -> Result<(), WlError> {
+        loop {
+            let wait = self.try_acquire_inner();
+            if wait.is_zero() {
+                return Ok(());
+            }
+            if self.mode == RateLimitMode::FailFast {
+                return Err(WlError::RateLimited { retry_after: wait });
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Takes one token if available without waiting.
+    pub fn try_acquire(&self) -> bool {
+        self.try_acquire_inner().is_zero()
+    }
+
+    fn try_acquire_inner(&self) -> Duration {
+        let mut buckets = self.buckets.lock().unwrap();
+        let now = Instant::now();
+        let mut wait = Duration::ZERO;
+        for bucket in buckets.iter_mut() {
+            bucket.refill(now);
+            wait = wait.max(bucket.time_until_available());
+        }
+        if wait.is_zero() {
+            for bucket in buckets.iter_mut() {
+                bucket.tokens -= 1.0;
+            }
+        }
+        wait
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    collections::HashSet,
+    time::{Duration, SystemTime},
+};
+
+use crate::{error::WlError, models::MessageCode, transport::UnrecordedRequest};
+
+/// Controls if and how often a failed request is sent again.
+///
+/// Delays grow exponentially from `base_delay` up to `max_delay`. A `Retry-After`
+/// header sent by the server takes precedence over the computed delay, but is also
+/// capped at `max_delay`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    pub(crate) max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    retryable_codes: HashSet<MessageCode>,
+    retryable_statuses: HashSet<u16>,
+    retry_transport_errors: bool,
+    respect_retry_after: bool,
+}
+
+impl RetryPolicy {
+    pub fn new() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+            jitter: 0.2,
+            retryable_codes: HashSet::from([
+                MessageCode::DbOffline,
+                MessageCode::RequestLimitExceeded,
+            ]),
+            retryable_statuses: HashSet::from([429, 500, 502, 503, 504]),
+            retry_transport_errors: true,
+            respect_retry_after: true,
+        }
+    }
+
+    /// Policy that sends every request exactly once.
+    pub fn none() -> Self {
+        RetryPolicy::new().max_attempts(1)
+    }
+
+    /// Total number of attempts including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Fraction (`0.0..=1.0`) of each delay that is randomly removed to spread out retries.
+    pub fn jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn retryable_codes(mut self, codes: impl IntoIterator<Item = MessageCode>) -> Self {
+        self.retryable_codes = codes.into_iter().collect();
+        self
+    }
+
+    pub fn retryable_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.retryable_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Whether connection failures and timeouts are retried.
+    pub fn retry_transport_errors(mut self, retry: bool) -> Self {
+        self.retry_transport_errors = retry;
+        self
+    }
+
+    pub fn respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    pub fn is_retryable(&self, error: &WlError) -> bool {
+        match error {
+            // a missing recording stays missing
+            WlError::Transport(err) if err.is::<UnrecordedRequest>() => false,
+            WlError::Transport(err) => {
+                self.retry_transport_errors
+                    && err
+                        .downcast_ref::<reqwest::Error>()
+                        .is_none_or(|err| err.is_connect() || err.is_timeout() || err.is_request())
+            }
+            WlError::HttpStatus { status, .. } => self.retryable_statuses.contains(status),
+            WlError::Api { code, .. } => self.retryable_codes.contains(code),
+            WlError::Decode { .. } | WlError::RateLimited { .. } | WlError::CircuitOpen { .. } => {
+                false
+            }
+        }
+    }
+
+    /// Delay before the retry following the given (1-based) failed attempt.
+    pub fn delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let (true, Some(retry_after)) = (self.respect_retry_after, retry_after) {
+            return retry_after.min(self.max_delay);
+        }
+
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        delay.mul_f64(1.0 - self.jitter * fastrand::f64())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new()
+    }
+}
+
+/// Parses a `Retry-After` header given either in seconds or as HTTP date.
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
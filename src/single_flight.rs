@@ -0,0 +1,70 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use tokio::sync::watch;
+
+use crate::error::WlError;
+
+type FlightResult = Option<Result<Bytes, WlError>>;
+
+/// Tracks in-flight requests by URL so concurrent identical requests share one HTTP call.
+#[derive(Debug, Default)]
+pub(crate) struct SingleFlight {
+    calls: Arc<Mutex<HashMap<String, watch::Receiver<FlightResult>>>>,
+}
+
+pub(crate) enum Flight {
+    /// No identical request is in flight, the caller has to send it and `complete` the guard.
+    Leader(FlightGuard),
+    /// An identical request is in flight, wait for its result.
+    Follower(watch::Receiver<FlightResult>),
+}
+
+impl SingleFlight {
+    pub(crate) fn join(&self, key: &str) -> Flight {
+        let mut calls = self.calls.lock().unwrap();
+        if let Some(receiver) = calls.get(key) {
+            return Flight::Follower(receiver.clone());
+        }
+        let (sender, receiver) = watch::channel(None);
+        calls.insert(key.to_owned(), receiver);
+        Flight::Leader(FlightGuard {
+            key: key.to_owned(),
+            calls: self.calls.clone(),
+            sender,
+        })
+    }
+
+    /// Number of distinct requests currently in flight.
+    pub(crate) fn len(&self) -> usize {
+        self.calls.lock().unwrap().len()
+    }
+}
+
+/// Removes the in-flight entry when the leader finishes or is cancelled.
+pub(crate) struct FlightGuard {
+    key: String,
+    calls: Arc<Mutex<HashMap<String, watch::Receiver<FlightResult>>>>,
+    sender: watch::Sender<FlightResult>,
+}
+
+impl FlightGuard {
+    pub(crate) fn complete(self, result: Result<Bytes, WlError>) {
+        self.sender.send_replace(Some(result));
+    }
+}
+
+impl Drop for FlightGuard {
+    fn drop(&mut self) {
+        self.calls.lock().unwrap().remove(&self.key);
+    }
+}
+
+/// Waits for the leader's result. `None` if the leader was cancelled before completing.
+pub(crate) async fn wait(mut receiver: watch::Receiver<FlightResult>) -> FlightResult {
+    let result = receiver.wait_for(Option::is_some).await.ok()?;
+    result.clone()
+}
@@ -0,0 +1,101 @@
+use std::{fmt::Debug, future::Future, io, time::Duration};
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+
+use crate::error::WlError;
+
+mod vcr;
+
+pub use vcr::{Cassette, Interaction, UnrecordedRequest, VcrMode, VcrTransport};
+
+/// Raw response as returned by a transport.
+///
+/// Headers are plain name/value pairs, so transports do not depend on the `http` crate
+/// version used by `reqwest`.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Bytes,
+}
+
+impl HttpResponse {
+    /// First value of a header, names are compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Sends the HTTP requests of a `WlClient`.
+///
+/// Implement this to use another HTTP stack, a mock or a recording transport.
+/// Failures to send the request or read the response should be returned as
+/// `WlError::Transport`, non-success statuses as a normal `HttpResponse`.
+#[async_trait]
+pub trait HttpTransport: Debug + Send + Sync {
+    async fn get(&self, url: &str) -> Result<HttpResponse, WlError>;
+}
+
+/// Default transport based on `reqwest`.
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    read_timeout: Option<Duration>,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport {
+            client,
+            read_timeout: None,
+        }
+    }
+
+    /// Maximum time to wait for the response head and for each chunk of the body.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    async fn with_read_timeout<T>(
+        &self,
+        future: impl Future<Output = Result<T, reqwest::Error>>,
+    ) -> Result<T, WlError> {
+        let Some(timeout) = self.read_timeout else {
+            return Ok(future.await?);
+        };
+        match tokio::time::timeout(timeout, future).await {
+            Ok(result) => Ok(result?),
+            Err(_) => Err(WlError::transport(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "read timed out",
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTransport for ReqwestTransport {
+    async fn get(&self, url: &str) -> Result<HttpResponse, WlError> {
+        let mut response = self.with_read_timeout(self.client.get(url).send()).await?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect();
+        let mut body = BytesMut::new();
+        while let Some(chunk) = self.with_read_timeout(response.chunk()).await? {
+            body.extend_from_slice(&chunk);
+        }
+        Ok(HttpResponse {
+            status,
+            headers,
+            body: body.freeze(),
+        })
+    }
+}
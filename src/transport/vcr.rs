@@ -0,0 +1,198 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use super::{HttpResponse, HttpTransport, ReqwestTransport};
+use crate::{error::WlError, serde_deserializers::date_format};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VcrMode {
+    /// Sends every request upstream and records it, replacing an existing cassette.
+    Record,
+    /// Replays recorded requests, unrecorded ones are sent upstream and added to the cassette.
+    Replay,
+    /// Replays recorded requests, unrecorded ones fail with an `UnrecordedRequest`
+    /// wrapped in `WlError::Transport`, which is never retried.
+    Strict,
+}
+
+/// Request missing from the cassette of a `VcrTransport` in `Strict` mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnrecordedRequest {
+    pub url: String,
+    pub cassette: PathBuf,
+}
+
+impl Display for UnrecordedRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "no recorded interaction for {} in {}",
+            self.url,
+            self.cassette.display()
+        )
+    }
+}
+
+impl Error for UnrecordedRequest {}
+
+/// A recorded request and its response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub url: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    #[serde(rename = "recordedAt", with = "date_format")]
+    pub recorded_at: DateTime<FixedOffset>,
+}
+
+impl Interaction {
+    fn record(url: &str, response: &HttpResponse) -> Self {
+        Interaction {
+            url: url.to_owned(),
+            status: response.status,
+            headers: response.headers.clone(),
+            body: String::from_utf8_lossy(&response.body).into_owned(),
+            recorded_at: Utc::now().fixed_offset(),
+        }
+    }
+
+    fn to_response(&self) -> HttpResponse {
+        HttpResponse {
+            status: self.status,
+            headers: self.headers.clone(),
+            body: Bytes::from(self.body.clone()),
+        }
+    }
+}
+
+/// Content of a cassette file.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+#[derive(Debug)]
+struct State {
+    cassette: Cassette,
+    /// Number of times each URL was replayed, to replay repeated requests in recorded order.
+    replayed: HashMap<String, usize>,
+}
+
+/// Transport recording responses into a cassette file and replaying them, for tests
+/// that must not depend on the live API.
+///
+/// Requests to the same URL are replayed in recorded order, the last recording is
+/// repeated once they are used up.
+#[derive(Debug)]
+pub struct VcrTransport {
+    path: PathBuf,
+    mode: VcrMode,
+    upstream: Arc<dyn HttpTransport>,
+    state: Mutex<State>,
+}
+
+impl VcrTransport {
+    /// Loads the cassette at `path`. It may only be missing in `Record` and `Replay` mode.
+    pub fn new(path: impl Into<PathBuf>, mode: VcrMode) -> io::Result<Self> {
+        let path = path.into();
+        let cassette = match mode {
+            VcrMode::Record => Cassette::default(),
+            VcrMode::Replay => match std::fs::read(&path) {
+                Ok(content) => serde_json::from_slice(&content)?,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => Cassette::default(),
+                Err(err) => return Err(err),
+            },
+            VcrMode::Strict => serde_json::from_slice(&std::fs::read(&path)?)?,
+        };
+
+        Ok(VcrTransport {
+            path,
+            mode,
+            upstream: Arc::new(ReqwestTransport::default()),
+            state: Mutex::new(State {
+                cassette,
+                replayed: HashMap::new(),
+            }),
+        })
+    }
+
+    /// Transport used for requests that are recorded, defaults to a `ReqwestTransport`.
+    pub fn upstream(mut self, upstream: Arc<dyn HttpTransport>) -> Self {
+        self.upstream = upstream;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn cassette(&self) -> Cassette {
+        self.state.lock().await.cassette.clone()
+    }
+
+    async fn replay(&self, url: &str) -> Option<HttpResponse> {
+        let mut state = self.state.lock().await;
+        let recorded: Vec<&Interaction> = state
+            .cassette
+            .interactions
+            .iter()
+            .filter(|interaction| interaction.url == url)
+            .collect();
+        let last = recorded.len().checked_sub(1)?;
+        let index = state.replayed.get(url).copied().unwrap_or(0);
+        let response = recorded[index.min(last)].to_response();
+        state.replayed.insert(url.to_owned(), index + 1);
+        Some(response)
+    }
+
+    async fn record(&self, url: &str) -> Result<HttpResponse, WlError> {
+        let response = self.upstream.get(url).await?;
+        let mut state = self.state.lock().await;
+        state
+            .cassette
+            .interactions
+            .push(Interaction::record(url, &response));
+        let content = serde_json::to_vec_pretty(&state.cassette)
+            .expect("cassette should always be serializable");
+        if let Some(dir) = self.path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .map_err(WlError::transport)?;
+        }
+        tokio::fs::write(&self.path, content)
+            .await
+            .map_err(WlError::transport)?;
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl HttpTransport for VcrTransport {
+    async fn get(&self, url: &str) -> Result<HttpResponse, WlError> {
+        if self.mode != VcrMode::Record {
+            if let Some(response) = self.replay(url).await {
+                return Ok(response);
+            }
+        }
+        match self.mode {
+            VcrMode::Strict => Err(WlError::transport(UnrecordedRequest {
+                url: url.to_owned(),
+                cassette: self.path.clone(),
+            })),
+            VcrMode::Record | VcrMode::Replay => self.record(url).await,
+        }
+    }
+}
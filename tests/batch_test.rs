@@ -0,0 +1,264 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{monitor_server, read_asset, request, MockReply};
+use wl_realtime_ogd::{
+    batch::{BatchConfig, MonitorBatcher},
+    client::WlClient,
+    error::WlError,
+    ids::Rbl,
+    mock_server::MockWlServer,
+    models::{ExtTrafficInfoEnum, MonitorRequest, TrafficInfoCategory, TrafficInfoEnum},
+};
+
+/// Fixture with two monitors for the stops 4711 and 1450.
+fn two_stop_response() -> String {
+    read_asset("monitor-response.json").replacen("\"rbl\": 1450", "\"rbl\": 4711", 1)
+}
+
+async fn batcher_for(server: &MockWlServer, config: BatchConfig) -> MonitorBatcher {
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+    MonitorBatcher::new(client, config)
+}
+
+#[tokio::test]
+async fn test_concurrent_requests_are_merged_and_split_by_rbl() {
+    let server = monitor_server(vec![MockReply::json(two_stop_response())]).await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new().window(Duration::from_millis(50)),
+    )
+    .await;
+
+    let first = request([4711]);
+    let second = request([1450]);
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+    let (first, second) = (first.unwrap(), second.unwrap());
+
+    assert_eq!(server.requests().len(), 1);
+    assert!(server.requests()[0].contains("stopId=4711"));
+    assert!(server.requests()[0].contains("stopId=1450"));
+    assert_eq!(first.data.monitors.len(), 1);
+    assert_eq!(
+        first.data.monitors[0]
+            .location_stop
+            .properties
+            .attributes
+            .rbl,
+        Rbl(4711)
+    );
+    assert_eq!(second.data.monitors.len(), 1);
+    assert_eq!(
+        second.data.monitors[0]
+            .location_stop
+            .properties
+            .attributes
+            .rbl,
+        Rbl(1450)
+    );
+}
+
+#[tokio::test]
+async fn test_traffic_infos_are_split_by_referenced_monitors() {
+    let server = monitor_server(vec![MockReply::json(two_stop_response())]).await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new().window(Duration::from_millis(50)),
+    )
+    .await;
+
+    let first = request([4711]);
+    let second = request([1450]);
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+
+    // only the monitor of stop 4711 references the traffic info
+    assert_eq!(first.unwrap().data.traffic_infos.unwrap().len(), 1);
+    assert!(second.unwrap().data.traffic_infos.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_traffic_info_categories_are_split_by_activated_traffic_info() {
+    let server = monitor_server(vec![MockReply::json(two_stop_response())]).await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new().window(Duration::from_millis(50)),
+    )
+    .await;
+
+    let mut first = request([4711]);
+    first
+        .activate_traffic_info
+        .push(ExtTrafficInfoEnum::TrafficInfo(
+            TrafficInfoEnum::StoerungKurz,
+        ));
+    let mut second = request([1450]);
+    second
+        .activate_traffic_info
+        .push(ExtTrafficInfoEnum::TrafficInfo(
+            TrafficInfoEnum::StoerungLang,
+        ));
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+    let (first, second) = (first.unwrap().data, second.unwrap().data);
+
+    assert_eq!(server.requests().len(), 1);
+    let names = |categories: Option<Vec<TrafficInfoCategory>>| -> Vec<String> {
+        categories
+            .unwrap()
+            .into_iter()
+            .map(|category| category.name)
+            .collect()
+    };
+    assert_eq!(names(first.traffic_info_categories), vec!["stoerungkurz"]);
+    assert_eq!(first.traffic_infos.unwrap().len(), 1);
+    assert_eq!(names(second.traffic_info_categories), vec!["stoerunglang"]);
+    assert!(second.traffic_infos.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_duplicate_stops_are_requested_once() {
+    let server = monitor_server(vec![MockReply::json(two_stop_response())]).await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new().window(Duration::from_millis(50)),
+    )
+    .await;
+
+    let first = request([4711]);
+    let second = request([4711]);
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+
+    assert_eq!(first.unwrap(), second.unwrap());
+    assert_eq!(server.requests(), vec!["/monitor?stopId=4711"]);
+}
+
+#[tokio::test]
+async fn test_requests_with_diva_are_not_merged() {
+    let server = monitor_server(vec![MockReply::json(two_stop_response())]).await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new().window(Duration::from_millis(50)),
+    )
+    .await;
+
+    let first = request([4711]);
+    let mut second = MonitorRequest::new();
+    second.diva = Some(60201035.into());
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+
+    assert!(first.is_ok());
+    assert_eq!(second.unwrap().data.monitors.len(), 2);
+    let mut requests = server.requests();
+    requests.sort();
+    assert_eq!(
+        requests,
+        vec!["/monitor?diva=60201035", "/monitor?stopId=4711"]
+    );
+}
+
+#[tokio::test]
+async fn test_batch_is_sent_when_max_stops_is_reached() {
+    let server = monitor_server(vec![MockReply::json(two_stop_response())]).await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new()
+            .window(Duration::from_secs(60))
+            .max_stops(2),
+    )
+    .await;
+
+    let first = request([4711]);
+    let second = request([1450]);
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_request_exceeding_max_stops_starts_next_batch() {
+    let server = monitor_server(vec![MockReply::json(two_stop_response())]).await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new()
+            .window(Duration::from_millis(50))
+            .max_stops(3),
+    )
+    .await;
+
+    let first = request([4711, 1450]);
+    let second = request([4711, 1450]);
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(
+        server.requests(),
+        vec![
+            "/monitor?stopId=4711&stopId=1450",
+            "/monitor?stopId=4711&stopId=1450"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_errors_are_returned_to_every_caller() {
+    let server = monitor_server(vec![MockReply::status(503)]).await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new().window(Duration::from_millis(50)),
+    )
+    .await;
+
+    let first = request([4711]);
+    let second = request([1450]);
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+
+    assert!(matches!(
+        first,
+        Err(WlError::HttpStatus { status: 503, .. })
+    ));
+    assert!(matches!(
+        second,
+        Err(WlError::HttpStatus { status: 503, .. })
+    ));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_unknown_stop_falls_back_to_single_requests() {
+    let server = monitor_server(vec![
+        MockReply::json(read_asset("monitor-response-error-312.json")),
+        MockReply::json(two_stop_response()),
+    ])
+    .await;
+    let batcher = batcher_for(
+        &server,
+        BatchConfig::new().window(Duration::from_millis(50)),
+    )
+    .await;
+
+    let first = request([4711]);
+    let second = request([1450]);
+    let (first, second) = tokio::join!(batcher.monitor(&first), batcher.monitor(&second));
+
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn test_batcher_fails_after_its_runtime_shut_down() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let batcher =
+        runtime.block_on(async { MonitorBatcher::new(WlClient::new(), BatchConfig::new()) });
+    drop(runtime);
+
+    let result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(batcher.monitor(&request([4711])));
+    assert!(matches!(result, Err(WlError::Transport(_))));
+}
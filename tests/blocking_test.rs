@@ -0,0 +1,144 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{monitor_server, read_asset, MockReply};
+use tokio::runtime::Runtime;
+use wl_realtime_ogd::{
+    blocking::WlClient,
+    client::Proxy,
+    error::WlError,
+    mock_server::MockWlServer,
+    models::{MessageCode, MonitorRequest, TrafficInfoListRequest},
+    retry::RetryPolicy,
+};
+
+/// The mock server needs a runtime, the returned one has to be kept alive by the test.
+fn start_server(responses: Vec<MockReply>) -> (Runtime, MockWlServer) {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()
+        .unwrap();
+    let server = runtime.block_on(monitor_server(responses));
+    (runtime, server)
+}
+
+fn client_for(server: &MockWlServer) -> WlClient {
+    WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_monitor_request() {
+    let (_runtime, server) =
+        start_server(vec![MockReply::json(read_asset("monitor-response.json"))]);
+    let client = client_for(&server);
+
+    let mut request = MonitorRequest::new();
+    request.stop_id = vec![4431.into()];
+    let response = client.monitor(&request).unwrap();
+    assert_eq!(response.data.monitors.len(), 2);
+    assert_eq!(server.requests(), vec!["/monitor?stopId=4431"]);
+}
+
+#[test]
+fn test_traffic_info_list_request() {
+    let (_runtime, server) = start_server(vec![]);
+    let client = client_for(&server);
+
+    let response = client
+        .traffic_info_list(&TrafficInfoListRequest::new())
+        .unwrap();
+    assert!(response.data.traffic_infos.is_some());
+}
+
+#[test]
+fn test_api_error() {
+    let (_runtime, server) = start_server(vec![MockReply::json(read_asset(
+        "monitor-response-error-312.json",
+    ))]);
+    let client = client_for(&server);
+
+    let error = client.monitor(&MonitorRequest::new()).unwrap_err();
+    assert!(matches!(
+        error,
+        WlError::Api {
+            code: MessageCode::StopDoesNotExist,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_retries_server_errors() {
+    let (_runtime, server) = start_server(vec![
+        MockReply::status(503),
+        MockReply::json(read_asset("monitor-response.json")),
+    ]);
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .retry_policy(
+            RetryPolicy::new()
+                .base_delay(Duration::from_millis(1))
+                .jitter(0.0),
+        )
+        .build()
+        .unwrap();
+
+    assert!(client.monitor(&MonitorRequest::new()).is_ok());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_requests_are_sent_through_proxy() {
+    let (_runtime, proxy) =
+        start_server(vec![MockReply::json(read_asset("monitor-response.json"))]);
+    let client = WlClient::builder()
+        .base_url("http://wl.invalid/ogd_realtime")
+        .proxy(Proxy::http(proxy.base_url()).unwrap())
+        .build()
+        .unwrap();
+
+    assert!(client.monitor(&MonitorRequest::new()).is_ok());
+    assert_eq!(
+        proxy.requests(),
+        vec!["http://wl.invalid/ogd_realtime/monitor?"]
+    );
+}
+
+#[test]
+fn test_read_timeout() {
+    let (_runtime, server) =
+        start_server(vec![
+            MockReply::json(read_asset("monitor-response.json")).delay(Duration::from_millis(500))
+        ]);
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .read_timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    let error = client.monitor(&MonitorRequest::new()).unwrap_err();
+    assert!(matches!(error, WlError::Transport(_)));
+}
+
+#[test]
+fn test_run_blocking_uses_default_client() {
+    let (_runtime, server) = start_server(vec![]);
+    // the only test changing the global default client of this file
+    wl_realtime_ogd::blocking::set_default_client(WlClient::builder().base_url(server.base_url()));
+
+    let mut request = MonitorRequest::new();
+    request.stop_id = vec![4431.into()];
+    assert_eq!(request.run_blocking().unwrap().data.monitors.len(), 2);
+    TrafficInfoListRequest::new().run_blocking().unwrap();
+    assert_eq!(
+        server.requests(),
+        vec!["/monitor?stopId=4431", "/trafficInfoList?"]
+    );
+}
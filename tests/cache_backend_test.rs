@@ -0,0 +1,188 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{client_with, monitor_server, read_asset, request, MockReply};
+use wl_realtime_ogd::{
+    cache::{CacheBackend, CacheConfig, CachedResponse, DiskBackend, MemoryBackend},
+    client::WlClient,
+    models::{MonitorRequest, MonitorResponse},
+};
+
+fn cached_response() -> CachedResponse {
+    let body = read_asset("monitor-response.json");
+    let response: MonitorResponse = serde_json::from_str(&body).unwrap();
+    CachedResponse {
+        body,
+        server_time: response.message.server_time,
+        stored_at: std::time::SystemTime::now(),
+    }
+}
+
+async fn assert_backend_round_trip(backend: &dyn CacheBackend) {
+    let entry = cached_response();
+    assert_eq!(backend.get("/monitor?stopId=1").await.unwrap(), None);
+
+    backend
+        .set("/monitor?stopId=1", &entry, Duration::from_secs(60))
+        .await
+        .unwrap();
+    let stored = backend.get("/monitor?stopId=1").await.unwrap().unwrap();
+    assert_eq!(stored.body, entry.body);
+    assert_eq!(stored.server_time, entry.server_time);
+    assert_eq!(backend.get("/monitor?stopId=2").await.unwrap(), None);
+
+    backend.clear().await.unwrap();
+    assert_eq!(backend.get("/monitor?stopId=1").await.unwrap(), None);
+}
+
+async fn assert_backend_expires_entries(backend: &dyn CacheBackend) {
+    backend
+        .set(
+            "/monitor?stopId=1",
+            &cached_response(),
+            Duration::from_millis(50),
+        )
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    assert_eq!(backend.get("/monitor?stopId=1").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_memory_backend() {
+    let backend = MemoryBackend::new(10);
+    assert_backend_round_trip(&backend).await;
+    assert_backend_expires_entries(&backend).await;
+}
+
+#[tokio::test]
+async fn test_memory_backend_evicts_least_recently_used() {
+    let backend = MemoryBackend::new(2);
+    let entry = cached_response();
+    let retention = Duration::from_secs(60);
+    backend.set("a", &entry, retention).await.unwrap();
+    backend.set("b", &entry, retention).await.unwrap();
+    backend.get("a").await.unwrap();
+    backend.set("c", &entry, retention).await.unwrap();
+
+    assert_eq!(backend.len(), 2);
+    assert_eq!(backend.evictions(), 1);
+    assert!(backend.get("a").await.unwrap().is_some());
+    assert!(backend.get("b").await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_disk_backend() {
+    let dir = tempfile::tempdir().unwrap();
+    let backend = DiskBackend::new(dir.path().join("cache"));
+    assert_backend_round_trip(&backend).await;
+    assert_backend_expires_entries(&backend).await;
+}
+
+#[tokio::test]
+async fn test_disk_backend_clear_keeps_other_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("settings.json"), "{}").unwrap();
+    let backend = DiskBackend::new(dir.path());
+    backend
+        .set(
+            "/monitor?stopId=1",
+            &cached_response(),
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+
+    backend.clear().await.unwrap();
+
+    let files: Vec<_> = std::fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(files, vec!["settings.json"]);
+}
+
+#[tokio::test]
+async fn test_disk_backend_is_shared_between_clients() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let dir = tempfile::tempdir().unwrap();
+    let client = || {
+        WlClient::builder()
+            .base_url(server.base_url())
+            .cache_backend(CacheConfig::new(), Arc::new(DiskBackend::new(dir.path())))
+            .build()
+            .unwrap()
+    };
+    let mut request = MonitorRequest::new();
+    request.stop_id.push(1.into());
+
+    let first = client().monitor(&request).await.unwrap();
+    let second = client().monitor(&request).await.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_disk_backend_prunes_expired_entries_of_other_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let backend = DiskBackend::new(dir.path()).prune_interval(Duration::ZERO);
+    let entry = cached_response();
+    backend
+        .set("/monitor?stopId=1", &entry, Duration::from_millis(50))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(80)).await;
+
+    backend
+        .set("/monitor?stopId=2", &entry, Duration::from_secs(60))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    assert!(backend.get("/monitor?stopId=2").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_shared_backend_keeps_responses_of_base_urls_apart() {
+    let staging = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let production =
+        monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let backend = Arc::new(MemoryBackend::new(10));
+
+    for server in [&staging, &production] {
+        let client = client_with(server, |builder| {
+            builder.cache_backend(CacheConfig::new(), backend.clone())
+        });
+        client.monitor(&request([1])).await.unwrap();
+        client.monitor(&request([1])).await.unwrap();
+    }
+
+    assert_eq!(staging.requests().len(), 1);
+    assert_eq!(production.requests().len(), 1);
+}
+
+#[cfg(feature = "redis")]
+#[tokio::test]
+#[ignore = "requires redis-server"]
+async fn test_redis_backend() {
+    use wl_realtime_ogd::cache::RedisBackend;
+
+    let url = std::env::var("WL_TEST_REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1/".into());
+    let backend = RedisBackend::new(&url)
+        .unwrap()
+        .prefix(format!("wl-test-{}:", unique_suffix()));
+    assert_backend_round_trip(&backend).await;
+    assert_backend_expires_entries(&backend).await;
+}
+
+#[cfg(feature = "redis")]
+fn unique_suffix() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos()
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{client_with, monitor_server, read_asset, request, MockReply};
+use wl_realtime_ogd::{
+    cache::{CacheConfig, CacheStats},
+    client::WlClient,
+    mock_server::MockWlServer,
+    models::TrafficInfoListRequest,
+};
+
+#[tokio::test]
+async fn test_cache_disabled_by_default() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    client.monitor(&request([1])).await.unwrap();
+    client.monitor(&request([1])).await.unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(client.cache_stats(), None);
+}
+
+#[tokio::test]
+async fn test_fresh_entries_are_served_from_cache() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = client_with(&server, |builder| builder.cache(CacheConfig::new()));
+
+    let first = client.monitor(&request([1])).await.unwrap();
+    let second = client.monitor(&request([1])).await.unwrap();
+    client.monitor(&request([2])).await.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(
+        client.cache_stats(),
+        Some(CacheStats {
+            hits: 1,
+            stale_hits: 0,
+            misses: 2,
+            evictions: 0,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_expired_entries_are_fetched_again() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = client_with(&server, |builder| {
+        builder.cache(CacheConfig::new().monitor_ttl(Duration::from_millis(50)))
+    });
+
+    client.monitor(&request([1])).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(80)).await;
+    client.monitor(&request([1])).await.unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_ttl_is_configured_per_endpoint() {
+    let server = MockWlServer::start().await.unwrap();
+    let client = client_with(&server, |builder| {
+        builder.cache(
+            CacheConfig::new()
+                .monitor_ttl(Duration::ZERO)
+                .traffic_info_list_ttl(Duration::from_secs(60)),
+        )
+    });
+
+    client.monitor(&request([1])).await.unwrap();
+    client
+        .traffic_info_list(&TrafficInfoListRequest::new())
+        .await
+        .unwrap();
+    client
+        .traffic_info_list(&TrafficInfoListRequest::new())
+        .await
+        .unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_least_recently_used_entry_is_evicted() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = client_with(&server, |builder| {
+        builder.cache(CacheConfig::new().max_entries(2))
+    });
+
+    client.monitor(&request([1])).await.unwrap();
+    client.monitor(&request([2])).await.unwrap();
+    client.monitor(&request([1])).await.unwrap();
+    client.monitor(&request([3])).await.unwrap();
+    assert_eq!(client.cache_stats().unwrap().evictions, 1);
+
+    // stop 2 was evicted, stop 1 is still cached
+    client.monitor(&request([1])).await.unwrap();
+    client.monitor(&request([2])).await.unwrap();
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn test_stale_entries_are_served_while_revalidating() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = client_with(&server, |builder| {
+        builder.cache(
+            CacheConfig::new()
+                .monitor_ttl(Duration::from_millis(300))
+                .stale_while_revalidate(Duration::from_secs(60)),
+        )
+    });
+
+    client.monitor(&request([1])).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(350)).await;
+    client.monitor(&request([1])).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    client.monitor(&request([1])).await.unwrap();
+
+    let stats = client.cache_stats().unwrap();
+    assert_eq!(stats.stale_hits, 1);
+    assert_eq!(stats.hits, 1);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_error_responses_are_not_cached() {
+    let server = monitor_server(vec![
+        MockReply::json(read_asset("monitor-response-error-311.json")),
+        MockReply::json(read_asset("monitor-response.json")),
+    ])
+    .await;
+    let client = client_with(&server, |builder| builder.cache(CacheConfig::new()));
+
+    assert!(client.monitor(&request([1])).await.is_err());
+    assert!(client.monitor(&request([1])).await.is_ok());
+    assert!(client.monitor(&request([1])).await.is_ok());
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_clear_cache() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = client_with(&server, |builder| builder.cache(CacheConfig::new()));
+
+    client.monitor(&request([1])).await.unwrap();
+    client.clear_cache().await;
+    client.monitor(&request([1])).await.unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
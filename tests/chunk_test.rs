@@ -0,0 +1,94 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use common::{client_for, monitor_server, read_asset, request, MockReply};
+use wl_realtime_ogd::{chunk::ChunkConfig, error::WlError, models::MonitorResponse};
+
+#[test]
+fn test_merge_deduplicates_traffic_infos_and_categories() {
+    let response: MonitorResponse =
+        serde_json::from_str(&read_asset("monitor-response.json")).unwrap();
+    let mut data = response.data.clone();
+    data.merge(response.data);
+
+    assert_eq!(data.monitors.len(), 4);
+    assert_eq!(data.traffic_infos.unwrap().len(), 1);
+    assert_eq!(data.traffic_info_categories.unwrap().len(), 2);
+    assert_eq!(data.traffic_info_category_groups.unwrap().len(), 1);
+}
+
+#[test]
+fn test_merge_into_empty_data() {
+    let response: MonitorResponse =
+        serde_json::from_str(&read_asset("monitor-response.json")).unwrap();
+    let empty: MonitorResponse =
+        serde_json::from_str(&read_asset("monitor-response-empty.json")).unwrap();
+    let mut data = empty.data;
+    data.merge(response.data.clone());
+
+    assert_eq!(data, response.data);
+}
+
+#[tokio::test]
+async fn test_small_request_is_sent_unchanged() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = client_for(&server);
+
+    let response = client
+        .monitor_chunked(&request([1, 2]), ChunkConfig::new().chunk_size(2))
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.monitors.len(), 2);
+    assert_eq!(server.requests(), vec!["/monitor?stopId=1&stopId=2"]);
+}
+
+#[tokio::test]
+async fn test_large_request_is_split_into_chunks() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = client_for(&server);
+
+    let response = client
+        .monitor_chunked(
+            &request(1..=5),
+            ChunkConfig::new().chunk_size(2).concurrency(2),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.monitors.len(), 6);
+    assert_eq!(response.data.traffic_infos.unwrap().len(), 1);
+    let mut requests = server.requests();
+    requests.sort();
+    assert_eq!(
+        requests,
+        vec![
+            "/monitor?stopId=1&stopId=2",
+            "/monitor?stopId=3&stopId=4",
+            "/monitor?stopId=5",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_chunk_error_fails_request() {
+    let server = monitor_server(vec![
+        MockReply::json(read_asset("monitor-response.json")),
+        MockReply::status(500),
+    ])
+    .await;
+    let client = client_for(&server);
+
+    let result = client
+        .monitor_chunked(
+            &request(1..=4),
+            ChunkConfig::new().chunk_size(2).concurrency(1),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(WlError::HttpStatus { status: 500, .. })
+    ));
+}
@@ -0,0 +1,182 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{client_with, monitor_server, read_asset, MockReply};
+use wl_realtime_ogd::{
+    circuit_breaker::{CircuitBreakerConfig, CircuitState},
+    client::WlClient,
+    error::WlError,
+    models::MonitorRequest,
+    rate_limit::{RateLimit, RateLimitMode},
+};
+
+#[tokio::test]
+async fn test_circuit_breaker_disabled_by_default() {
+    let client = WlClient::new();
+    assert_eq!(client.circuit_state(), None);
+}
+
+#[tokio::test]
+async fn test_circuit_opens_after_consecutive_failures() {
+    let server = monitor_server(vec![MockReply::status(500)]).await;
+    let client = client_with(&server, |builder| {
+        builder.circuit_breaker(CircuitBreakerConfig::new().failure_threshold(3))
+    });
+    let request = MonitorRequest::new();
+
+    for _ in 0..3 {
+        assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+        let error = client.monitor(&request).await.unwrap_err();
+        assert!(matches!(error, WlError::HttpStatus { status: 500, .. }));
+    }
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+
+    let error = client.monitor(&request).await.unwrap_err();
+    assert!(matches!(error, WlError::CircuitOpen { .. }));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_db_offline_counts_as_failure() {
+    let server = monitor_server(vec![MockReply::json(read_asset(
+        "monitor-response-error-311.json",
+    ))])
+    .await;
+    let client = client_with(&server, |builder| {
+        builder.circuit_breaker(CircuitBreakerConfig::new().failure_threshold(2))
+    });
+
+    client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    client.monitor(&MonitorRequest::new()).await.unwrap_err();
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+}
+
+#[tokio::test]
+async fn test_success_resets_failure_count() {
+    let server = monitor_server(vec![
+        MockReply::status(500),
+        MockReply::json(read_asset("monitor-response.json")),
+        MockReply::status(500),
+    ])
+    .await;
+    let client = client_with(&server, |builder| {
+        builder.circuit_breaker(CircuitBreakerConfig::new().failure_threshold(2))
+    });
+    let request = MonitorRequest::new();
+
+    client.monitor(&request).await.unwrap_err();
+    client.monitor(&request).await.unwrap();
+    client.monitor(&request).await.unwrap_err();
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[tokio::test]
+async fn test_permanent_api_errors_do_not_open_circuit() {
+    let server = monitor_server(vec![MockReply::json(read_asset(
+        "monitor-response-error-312.json",
+    ))])
+    .await;
+    let client = client_with(&server, |builder| {
+        builder.circuit_breaker(CircuitBreakerConfig::new().failure_threshold(1))
+    });
+
+    client.monitor(&MonitorRequest::new()).await.unwrap_err();
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[tokio::test]
+async fn test_half_open_probe_closes_circuit_on_success() {
+    let server = monitor_server(vec![
+        MockReply::status(503),
+        MockReply::json(read_asset("monitor-response.json")),
+    ])
+    .await;
+    let client = client_with(&server, |builder| {
+        builder.circuit_breaker(
+            CircuitBreakerConfig::new()
+                .failure_threshold(1)
+                .cool_down(Duration::from_millis(100)),
+        )
+    });
+    let request = MonitorRequest::new();
+
+    client.monitor(&request).await.unwrap_err();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(client.circuit_state(), Some(CircuitState::HalfOpen));
+
+    client.monitor(&request).await.unwrap();
+    assert_eq!(client.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[tokio::test]
+async fn test_half_open_probe_reopens_circuit_on_failure() {
+    let server = monitor_server(vec![MockReply::status(503)]).await;
+    let client = client_with(&server, |builder| {
+        builder.circuit_breaker(
+            CircuitBreakerConfig::new()
+                .failure_threshold(1)
+                .cool_down(Duration::from_millis(100)),
+        )
+    });
+    let request = MonitorRequest::new();
+
+    client.monitor(&request).await.unwrap_err();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    client.monitor(&request).await.unwrap_err();
+
+    assert_eq!(client.circuit_state(), Some(CircuitState::Open));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_open_circuit_reports_remaining_cool_down() {
+    let server = monitor_server(vec![MockReply::status(503)]).await;
+    let client = client_with(&server, |builder| {
+        builder.circuit_breaker(
+            CircuitBreakerConfig::new()
+                .failure_threshold(1)
+                .cool_down(Duration::from_secs(60)),
+        )
+    });
+    let request = MonitorRequest::new();
+
+    client.monitor(&request).await.unwrap_err();
+    match client.monitor(&request).await {
+        Err(WlError::CircuitOpen { retry_after }) => {
+            assert!(retry_after > Duration::from_secs(50));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limited_request_does_not_close_circuit() {
+    let server = monitor_server(vec![MockReply::status(503)]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .circuit_breaker(
+            CircuitBreakerConfig::new()
+                .failure_threshold(1)
+                .cool_down(Duration::from_millis(100)),
+        )
+        .rate_limit(RateLimit::new().per_minute(1).mode(RateLimitMode::FailFast))
+        .build()
+        .unwrap();
+    let request = MonitorRequest::new();
+
+    client.monitor(&request).await.unwrap_err();
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(client.circuit_state(), Some(CircuitState::HalfOpen));
+
+    let error = client.monitor(&request).await.unwrap_err();
+    assert!(matches!(error, WlError::RateLimited { .. }));
+    assert_eq!(client.circuit_state(), Some(CircuitState::HalfOpen));
+    assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,195 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{monitor_server, read_asset, MockReply};
+use reqwest::header::{HeaderName, HeaderValue};
+use wl_realtime_ogd::{
+    cache::Endpoint,
+    client::{Proxy, WlClient, WL_ENDPOINT},
+    error::WlError,
+    mock_server::MockWlServer,
+    models::{MessageCode, MonitorRequest, TrafficInfoEnum, TrafficInfoListRequest},
+};
+
+#[test]
+fn test_default_client_uses_wl_endpoint() {
+    let client = WlClient::new();
+    assert_eq!(WL_ENDPOINT, client.base_url());
+}
+
+#[test]
+fn test_builder_trims_trailing_slash_from_base_url() {
+    let client = WlClient::builder()
+        .base_url("http://localhost:8080/")
+        .build()
+        .unwrap();
+    assert_eq!("http://localhost:8080", client.base_url());
+}
+
+#[tokio::test]
+async fn test_monitor_uses_configured_base_url() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let mut request = MonitorRequest::new();
+    request.stop_id.push(1450.into());
+    let response = client.monitor(&request).await.unwrap();
+
+    assert_eq!(response.data.monitors.len(), 2);
+    assert_eq!(server.requests(), vec!["/monitor?stopId=1450"]);
+}
+
+#[tokio::test]
+async fn test_traffic_info_list_uses_configured_base_url() {
+    let server = MockWlServer::start().await.unwrap();
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .user_agent("wl-test")
+        .default_header(
+            HeaderName::from_static("x-test"),
+            HeaderValue::from_static("1"),
+        )
+        .build()
+        .unwrap();
+
+    let mut request = TrafficInfoListRequest::new();
+    request.name.push(TrafficInfoEnum::AufzugsInfo);
+    let response = client.traffic_info_list(&request).await.unwrap();
+
+    assert_eq!(response.data.traffic_infos.unwrap().len(), 17);
+    assert_eq!(server.requests(), vec!["/trafficInfoList?name=aufzugsinfo"]);
+}
+
+#[tokio::test]
+async fn test_cloned_clients_share_configuration() {
+    let server = monitor_server(vec![MockReply::json(read_asset(
+        "monitor-response-empty.json",
+    ))])
+    .await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+    let clone = client.clone();
+
+    let request = MonitorRequest::new();
+    client.monitor(&request).await.unwrap();
+    clone.monitor(&request).await.unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_monitor_returns_api_error_for_non_ok_message_code() {
+    let body =
+        read_asset("monitor-response.json").replace("\"messageCode\": 1", "\"messageCode\": 312");
+    let server = monitor_server(vec![MockReply::json(body)]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    match error {
+        WlError::Api { code, .. } => assert_eq!(code, MessageCode::StopDoesNotExist),
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_message_code_check_can_be_disabled() {
+    let body =
+        read_asset("monitor-response.json").replace("\"messageCode\": 1", "\"messageCode\": 312");
+    let server = monitor_server(vec![MockReply::json(body)]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .check_message_code(false)
+        .build()
+        .unwrap();
+
+    let response = client.monitor(&MonitorRequest::new()).await.unwrap();
+    assert_eq!(response.message.message_code, MessageCode::StopDoesNotExist);
+}
+
+#[tokio::test]
+async fn test_error_response_without_data_reports_message_code() {
+    let server = monitor_server(vec![MockReply::json(read_asset(
+        "monitor-response-error-316.json",
+    ))])
+    .await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    match error {
+        WlError::Api { code, message } => {
+            assert_eq!(code, MessageCode::RequestLimitExceeded);
+            assert_eq!(message, "Maximale Anzahl an Anfragen überschritten");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_error_response_with_malformed_data_reports_message_code() {
+    let body = r#"{"data":{"trafficInfos":"broken"},"message":{"value":"Fehlender GET-Parameter","messageCode":321,"serverTime":"2023-05-29T03:15:16.000+0200"}}"#;
+    let server = MockWlServer::start().await.unwrap();
+    server.enqueue(Endpoint::TrafficInfoList, MockReply::json(body));
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let error = client
+        .traffic_info_list(&TrafficInfoListRequest::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        WlError::Api {
+            code: MessageCode::GetParamMissing,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_requests_are_sent_through_proxy() {
+    let proxy = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = WlClient::builder()
+        .base_url("http://wl.invalid/ogd_realtime")
+        .proxy(Proxy::http(proxy.base_url()).unwrap())
+        .build()
+        .unwrap();
+
+    assert!(client.monitor(&MonitorRequest::new()).await.is_ok());
+    assert_eq!(
+        proxy.requests(),
+        vec!["http://wl.invalid/ogd_realtime/monitor?"]
+    );
+}
+
+#[tokio::test]
+async fn test_read_timeout() {
+    let server = monitor_server(vec![
+        MockReply::json(read_asset("monitor-response.json")).delay(Duration::from_millis(500))
+    ])
+    .await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .read_timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    assert!(matches!(error, WlError::Transport(_)));
+    assert_eq!(error.to_string(), "transport error: read timed out");
+}
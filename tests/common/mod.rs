@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+
+use std::{fs::File, io::Read};
+
+use wl_realtime_ogd::{
+    cache::Endpoint,
+    client::{WlClient, WlClientBuilder},
+    ids::Rbl,
+    mock_server::MockWlServer,
+    models::MonitorRequest,
+};
+
+pub use wl_realtime_ogd::mock_server::MockReply;
+
+/// Starts a mock server answering `/monitor` with `replies` in order, the last one is repeated.
+pub async fn monitor_server(replies: Vec<MockReply>) -> MockWlServer {
+    let server = MockWlServer::start().await.unwrap();
+    server.set_replies(Endpoint::Monitor, replies);
+    server
+}
+
+/// Client sending its requests to `server`.
+pub fn client_for(server: &MockWlServer) -> WlClient {
+    client_with(server, |builder| builder)
+}
+
+/// Client sending its requests to `server`, with further options set by `configure`.
+pub fn client_with(
+    server: &MockWlServer,
+    configure: impl FnOnce(WlClientBuilder) -> WlClientBuilder,
+) -> WlClient {
+    configure(WlClient::builder().base_url(server.base_url()))
+        .build()
+        .unwrap()
+}
+
+/// Monitor request for the given stops.
+pub fn request(stop_ids: impl IntoIterator<Item = u32>) -> MonitorRequest {
+    let mut request = MonitorRequest::new();
+    request.stop_id.extend(stop_ids.into_iter().map(Rbl));
+    request
+}
+
+pub fn read_asset(name: &str) -> String {
+    let mut file = File::open(format!("./tests/assets/{}", name)).unwrap();
+    let mut buffer: String = String::new();
+    file.read_to_string(&mut buffer).unwrap();
+    buffer
+}
@@ -0,0 +1,201 @@
+use std::{error::Error, fmt::Display, fs};
+
+use wl_realtime_ogd::{
+    config::{load_request_set, load_request_set_with, RequestConfig, RequestSet},
+    error::{ConfigError, RequestValidationError},
+    ids::Diva,
+    models::{ExtTrafficInfoEnum, MonitorRequest, TrafficInfoEnum, TrafficInfoListRequest},
+};
+
+const CONFIG: &str = r#"{
+    "westbahnhof": {
+        "monitor": [
+            { "stopId": [4431, "4432"], "activateTrafficInfo": ["stoerunglang", "information"] },
+            { "diva": 60201076, "aArea": true }
+        ]
+    },
+    "elevators": {
+        "trafficInfoList": [{ "name": ["aufzugsinfo"], "relatedLine": ["U3"], "relatedStop": [4900] }]
+    },
+    "broken": {
+        "monitor": [{}]
+    }
+}"#;
+
+fn write_config(content: &str) -> tempfile::NamedTempFile {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    fs::write(file.path(), content).unwrap();
+    file
+}
+
+#[test]
+fn test_traffic_info_uses_api_names() {
+    assert_eq!(
+        serde_json::to_string(&TrafficInfoEnum::FahrtreppenInfo).unwrap(),
+        r#""fahrtreppeninfo""#
+    );
+    assert_eq!(
+        serde_json::to_string(&ExtTrafficInfoEnum::Information).unwrap(),
+        r#""information""#
+    );
+    assert_eq!(
+        serde_json::from_str::<ExtTrafficInfoEnum>(r#""stoerungkurz""#).unwrap(),
+        ExtTrafficInfoEnum::TrafficInfo(TrafficInfoEnum::StoerungKurz)
+    );
+    assert!(serde_json::from_str::<TrafficInfoEnum>(r#""StoerungKurz""#).is_err());
+}
+
+#[test]
+fn test_requests_round_trip() {
+    let monitor = MonitorRequest::builder()
+        .stop_ids([4431, 4432])
+        .diva(60201076)
+        .activate_traffic_info(ExtTrafficInfoEnum::Information)
+        .a_area(true)
+        .build()
+        .unwrap();
+    let serialized = serde_json::to_string(&monitor).unwrap();
+    assert_eq!(
+        serialized,
+        r#"{"stopId":[4431,4432],"diva":60201076,"activateTrafficInfo":["information"],"aArea":true}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<MonitorRequest>(&serialized).unwrap(),
+        monitor
+    );
+
+    let traffic_info = TrafficInfoListRequest::builder()
+        .related_line("U6")
+        .name(TrafficInfoEnum::AufzugsInfo)
+        .build()
+        .unwrap();
+    let serialized = serde_json::to_string(&traffic_info).unwrap();
+    assert_eq!(
+        serde_json::from_str::<TrafficInfoListRequest>(&serialized).unwrap(),
+        traffic_info
+    );
+}
+
+#[test]
+fn test_missing_fields_default_to_empty() {
+    assert_eq!(
+        serde_json::from_str::<MonitorRequest>("{}").unwrap(),
+        MonitorRequest::new()
+    );
+    assert_eq!(
+        serde_json::from_str::<TrafficInfoListRequest>("{}").unwrap(),
+        TrafficInfoListRequest::new()
+    );
+}
+
+#[test]
+fn test_load_request_set() {
+    let file = write_config(CONFIG);
+
+    let set = load_request_set(file.path(), "westbahnhof").unwrap();
+    assert_eq!(set.monitor.len(), 2);
+    assert!(set.traffic_info_list.is_empty());
+    assert_eq!(set.monitor[0].stop_id, vec![4431.into(), 4432.into()]);
+    assert_eq!(
+        set.monitor[0].activate_traffic_info,
+        vec![
+            ExtTrafficInfoEnum::TrafficInfo(TrafficInfoEnum::StoerungLang),
+            ExtTrafficInfoEnum::Information,
+        ]
+    );
+    assert_eq!(set.monitor[1].diva, Some(Diva(60201076)));
+    assert!(set.monitor[1].a_area);
+
+    let set = load_request_set(file.path(), "elevators").unwrap();
+    assert_eq!(
+        set.traffic_info_list[0].name,
+        vec![TrafficInfoEnum::AufzugsInfo]
+    );
+    assert_eq!(set.traffic_info_list[0].related_line, vec!["U3".into()]);
+}
+
+#[test]
+fn test_load_request_set_errors() {
+    let file = write_config(CONFIG);
+    assert!(matches!(
+        load_request_set(file.path(), "missing"),
+        Err(ConfigError::UnknownSet(name)) if name == "missing"
+    ));
+    assert!(matches!(
+        load_request_set(file.path(), "broken"),
+        Err(ConfigError::InvalidRequest {
+            source: RequestValidationError::MissingParameter(_),
+            ..
+        })
+    ));
+
+    let file = write_config(r#"{"set": {"monitor": [{"activateTrafficInfo": ["unknown"]}]}}"#);
+    assert!(matches!(
+        RequestConfig::from_file(file.path()),
+        Err(ConfigError::Parse(_))
+    ));
+    assert!(matches!(
+        RequestConfig::from_file(file.path().with_extension("missing")),
+        Err(ConfigError::Io(_))
+    ));
+}
+
+#[derive(Debug)]
+struct LineError(String);
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid line `{}`", self.0)
+    }
+}
+
+impl Error for LineError {}
+
+/// Stand-in for TOML or YAML: one `set=stop,stop` line per monitor request.
+fn parse_lines(content: &str) -> Result<RequestConfig, LineError> {
+    let mut config = RequestConfig::default();
+    for line in content.lines() {
+        let (name, stops) = line.split_once('=').ok_or(LineError(line.to_owned()))?;
+        let request = MonitorRequest::builder()
+            .stop_ids(
+                stops
+                    .split(',')
+                    .map(|stop| stop.parse::<u32>().map_err(|_| LineError(line.to_owned())))
+                    .collect::<Result<Vec<_>, _>>()?,
+            )
+            .build()
+            .map_err(|_| LineError(line.to_owned()))?;
+        config
+            .sets
+            .entry(name.to_owned())
+            .or_insert_with(RequestSet::default)
+            .monitor
+            .push(request);
+    }
+    Ok(config)
+}
+
+#[test]
+fn test_load_request_set_with_custom_format() {
+    let file = write_config("westbahnhof=4431,4432\nwestbahnhof=1450");
+
+    let set = load_request_set_with(file.path(), "westbahnhof", parse_lines).unwrap();
+    assert_eq!(set.monitor.len(), 2);
+    assert_eq!(set.monitor[1].stop_id, vec![1450.into()]);
+
+    let file = write_config("westbahnhof");
+    let error = RequestConfig::from_file_with(file.path(), parse_lines).unwrap_err();
+    assert!(matches!(error, ConfigError::Parse(_)));
+    assert_eq!(
+        error.source().unwrap().to_string(),
+        "invalid line `westbahnhof`"
+    );
+}
+
+#[test]
+fn test_config_from_deserializer() {
+    let mut deserializer = serde_json::Deserializer::from_str(CONFIG);
+    let config = RequestConfig::from_deserializer(&mut deserializer).unwrap();
+    assert_eq!(config.sets.len(), 3);
+    assert!(config.get("elevators").is_some());
+}
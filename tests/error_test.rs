@@ -0,0 +1,78 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::error::Error;
+
+use common::{monitor_server, MockReply};
+use wl_realtime_ogd::{client::WlClient, error::WlError, models::MonitorRequest};
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+#[test]
+fn test_error_is_send_sync() {
+    assert_send_sync::<WlError>();
+    let _: Box<dyn Error + Send + Sync> = Box::new(WlError::HttpStatus {
+        status: 500,
+        body: String::new(),
+    });
+}
+
+async fn run_against(response: MockReply) -> WlError {
+    let server = monitor_server(vec![response]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+    client.monitor(&MonitorRequest::new()).await.unwrap_err()
+}
+
+#[tokio::test]
+async fn test_http_status_error_contains_status_and_body() {
+    let error = run_against(MockReply::status(503).body("Service Unavailable")).await;
+    match error {
+        WlError::HttpStatus { status, body } => {
+            assert_eq!(status, 503);
+            assert_eq!(body, "Service Unavailable");
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_http_status_error_truncates_long_body() {
+    let error = run_against(MockReply::status(500).body("x".repeat(1000))).await;
+    match error {
+        WlError::HttpStatus { body, .. } => assert_eq!(body.len(), 256 + 3),
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_decode_error_reports_path_of_failing_field() {
+    let error = run_against(MockReply::json(
+        r#"{"data":{"monitors":[]},"message":{"value":"OK","messageCode":1,"serverTime":"not a date"}}"#,
+    ))
+    .await;
+    match &error {
+        WlError::Decode { path, .. } => assert_eq!(path, "message.serverTime"),
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(error.source().is_some());
+}
+
+#[tokio::test]
+async fn test_decode_error_for_invalid_json() {
+    let error = run_against(MockReply::json("<html>")).await;
+    assert!(matches!(error, WlError::Decode { .. }));
+}
+
+#[tokio::test]
+async fn test_transport_error_when_server_unreachable() {
+    let client = WlClient::builder()
+        .base_url("http://127.0.0.1:1")
+        .build()
+        .unwrap();
+    let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    assert!(matches!(error, WlError::Transport(_)));
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::collections::BTreeSet;
+
+use common::read_asset;
+use wl_realtime_ogd::{
+    ids::{Diva, LineName, Rbl, TrafficInfoName},
+    models::{MonitorRequest, MonitorResponse},
+};
+
+#[test]
+fn test_display_and_from_str() {
+    assert_eq!(Rbl(4431).to_string(), "4431");
+    assert_eq!("4431".parse::<Rbl>(), Ok(Rbl(4431)));
+    assert!("U1".parse::<Rbl>().is_err());
+    assert_eq!("60201035".parse::<Diva>(), Ok(Diva(60201035)));
+    assert_eq!(LineName::new("U1").to_string(), "U1");
+    assert_eq!("U1".parse::<LineName>(), Ok(LineName::from("U1")));
+    assert_eq!(TrafficInfoName::new("tk_1").as_str(), "tk_1");
+}
+
+#[test]
+fn test_serde() {
+    assert_eq!(serde_json::to_string(&Rbl(4431)).unwrap(), "4431");
+    assert_eq!(serde_json::from_str::<Rbl>("4431").unwrap(), Rbl(4431));
+    assert_eq!(
+        serde_json::from_str::<Diva>("\"60201035\"").unwrap(),
+        Diva(60201035)
+    );
+    assert!(serde_json::from_str::<Diva>("\"abc\"").is_err());
+    assert_eq!(
+        serde_json::to_string(&LineName::new("U1")).unwrap(),
+        "\"U1\""
+    );
+    assert_eq!(
+        serde_json::from_str::<LineName>("\"U1\"").unwrap(),
+        LineName::new("U1")
+    );
+}
+
+#[test]
+fn test_ordering() {
+    let stops: BTreeSet<Rbl> = [Rbl(3), Rbl(1), Rbl(2), Rbl(1)].into_iter().collect();
+    assert_eq!(
+        stops.into_iter().collect::<Vec<_>>(),
+        vec![Rbl(1), Rbl(2), Rbl(3)]
+    );
+}
+
+#[test]
+fn test_ids_join_requests_and_responses() {
+    let response: MonitorResponse =
+        serde_json::from_str(&read_asset("monitor-response.json")).unwrap();
+    let monitor = &response.data.monitors[0];
+    assert_eq!(monitor.location_stop.properties.name, Diva(60201035));
+    assert_eq!(monitor.lines[0].name, LineName::new("N49"));
+
+    let mut request = MonitorRequest::new();
+    request.stop_id.push(Rbl(1450));
+    assert!(request
+        .stop_id
+        .contains(&monitor.location_stop.properties.attributes.rbl));
+
+    let traffic_infos = response.data.traffic_infos.unwrap();
+    let referenced = monitor.ref_traffic_info_names.as_ref().unwrap();
+    assert!(traffic_infos
+        .iter()
+        .any(|info| referenced.contains(&info.name)));
+}
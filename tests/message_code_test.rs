@@ -0,0 +1,66 @@
+use wl_realtime_ogd::models::{Message, MessageCode};
+
+#[test]
+fn test_deserialize_known_code() {
+    let code: MessageCode = serde_json::from_str("316").unwrap();
+    assert_eq!(code, MessageCode::RequestLimitExceeded);
+}
+
+#[test]
+fn test_deserialize_unknown_code() {
+    let code: MessageCode = serde_json::from_str("999").unwrap();
+    assert_eq!(code, MessageCode::Unknown(999));
+    assert_eq!(code.code(), 999);
+}
+
+#[test]
+fn test_message_with_unknown_code_parses() {
+    let message: Message = serde_json::from_str(
+        r#"{"value":"Neu","messageCode":330,"serverTime":"2023-05-29T03:15:16.000+0200"}"#,
+    )
+    .unwrap();
+    assert_eq!(message.message_code, MessageCode::Unknown(330));
+    assert!(!message.is_ok());
+}
+
+#[test]
+fn test_code_round_trip() {
+    for code in [1, 311, 312, 316, 320, 321, 322, 42] {
+        assert_eq!(MessageCode::from_code(code).code(), code);
+        assert_eq!(u32::from(MessageCode::from(code)), code);
+    }
+}
+
+#[test]
+fn test_classification() {
+    assert!(MessageCode::OK.is_success());
+    assert!(!MessageCode::NoDataFound.is_success());
+    assert!(MessageCode::DbOffline.is_retryable());
+    assert!(MessageCode::RequestLimitExceeded.is_retryable());
+    assert!(!MessageCode::StopDoesNotExist.is_retryable());
+    assert!(!MessageCode::Unknown(500).is_retryable());
+}
+
+#[test]
+fn test_display() {
+    assert_eq!(
+        MessageCode::StopDoesNotExist.to_string(),
+        "312 Haltepunkt existiert nicht (stop does not exist)"
+    );
+    assert_eq!(
+        MessageCode::Unknown(400).to_string(),
+        "400 Unbekannter Code (unknown code)"
+    );
+}
+
+#[test]
+fn test_serialize_code() {
+    assert_eq!(
+        serde_json::to_string(&MessageCode::RequestLimitExceeded).unwrap(),
+        "316"
+    );
+    assert_eq!(
+        serde_json::to_string(&MessageCode::Unknown(999)).unwrap(),
+        "999"
+    );
+}
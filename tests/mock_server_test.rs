@@ -0,0 +1,144 @@
+#![cfg(feature = "mock-server")]
+
+use std::time::{Duration, Instant};
+
+use wl_realtime_ogd::{
+    cache::Endpoint,
+    client::WlClient,
+    error::WlError,
+    mock_server::{MockReply, MockWlServer},
+    models::{MessageCode, MonitorRequest, TrafficInfoListRequest},
+};
+
+fn client_for(server: &MockWlServer) -> WlClient {
+    WlClient::builder()
+        .base_url(server.base_url())
+        .deduplicate_requests(false)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_serves_fixtures() {
+    let server = MockWlServer::start().await.unwrap();
+    let client = client_for(&server);
+
+    let monitor = client.monitor(&MonitorRequest::new()).await.unwrap();
+    assert_eq!(monitor.data.monitors.len(), 2);
+    let traffic_infos = client
+        .traffic_info_list(&TrafficInfoListRequest::new())
+        .await
+        .unwrap();
+    assert!(traffic_infos.data.traffic_infos.is_some());
+    assert_eq!(server.requests(), vec!["/monitor?", "/trafficInfoList?"]);
+}
+
+#[tokio::test]
+async fn test_custom_fixture() {
+    let server = MockWlServer::start().await.unwrap();
+    server.set_fixture(
+        Endpoint::Monitor,
+        include_str!("assets/monitor-response-empty.json"),
+    );
+    let client = client_for(&server);
+
+    let monitor = client.monitor(&MonitorRequest::new()).await.unwrap();
+    assert!(monitor.data.monitors.is_empty());
+}
+
+#[tokio::test]
+async fn test_replies_are_served_in_order_and_last_one_repeated() {
+    let server = MockWlServer::start().await.unwrap();
+    server.set_replies(
+        Endpoint::Monitor,
+        [
+            MockReply::status(503).body("unavailable"),
+            MockReply::fixture(),
+        ],
+    );
+    let client = client_for(&server);
+
+    let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    assert!(matches!(
+        error,
+        WlError::HttpStatus { status: 503, ref body } if body == "unavailable"
+    ));
+    assert!(client.monitor(&MonitorRequest::new()).await.is_ok());
+    assert!(client.monitor(&MonitorRequest::new()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_simulates_message_codes() {
+    let server = MockWlServer::start().await.unwrap();
+    let client = client_for(&server);
+    let codes = [
+        MessageCode::DbOffline,
+        MessageCode::StopDoesNotExist,
+        MessageCode::RequestLimitExceeded,
+        MessageCode::GetParamInvalid,
+        MessageCode::GetParamMissing,
+        MessageCode::NoDataFound,
+        MessageCode::Unknown(399),
+    ];
+
+    for code in codes {
+        server.enqueue(Endpoint::Monitor, MockReply::message_code(code));
+        let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+        assert!(matches!(error, WlError::Api { code: actual, .. } if actual == code));
+    }
+    server.enqueue(Endpoint::Monitor, MockReply::message_code(MessageCode::OK));
+    assert!(client.monitor(&MonitorRequest::new()).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_simulates_http_errors_and_malformed_bodies() {
+    let server = MockWlServer::start().await.unwrap();
+    server.enqueue(
+        Endpoint::TrafficInfoList,
+        MockReply::status(429).header("Retry-After", "1"),
+    );
+    server.enqueue(Endpoint::TrafficInfoList, MockReply::status(503));
+    server.enqueue(Endpoint::TrafficInfoList, MockReply::malformed());
+    let client = client_for(&server);
+    let request = TrafficInfoListRequest::new();
+
+    let error = client.traffic_info_list(&request).await.unwrap_err();
+    assert!(matches!(error, WlError::HttpStatus { status: 429, .. }));
+    let error = client.traffic_info_list(&request).await.unwrap_err();
+    assert!(matches!(error, WlError::HttpStatus { status: 503, .. }));
+    let error = client.traffic_info_list(&request).await.unwrap_err();
+    assert!(matches!(error, WlError::Decode { .. }));
+    assert!(client.traffic_info_list(&request).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_default_reply_and_unknown_paths() {
+    let server = MockWlServer::start().await.unwrap();
+    server.set_default(Endpoint::Monitor, MockReply::status(500));
+    let client = client_for(&server);
+
+    for _ in 0..2 {
+        let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+        assert!(matches!(error, WlError::HttpStatus { status: 500, .. }));
+    }
+    let response = reqwest::get(format!("{}/departures", server.base_url()))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_simulates_latency() {
+    let server = MockWlServer::start().await.unwrap();
+    server.set_latency(Duration::from_millis(100));
+    server.enqueue(
+        Endpoint::Monitor,
+        MockReply::fixture().delay(Duration::from_millis(100)),
+    );
+    let client = client_for(&server);
+
+    let started = Instant::now();
+    client.monitor(&MonitorRequest::new()).await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(200));
+}
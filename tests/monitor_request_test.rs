@@ -1,7 +1,11 @@
 use std::{fs::File, io::Read};
 
-use wl_realtime_ogd::models::{
-    BuildRequestUrl, ExtTrafficInfoEnum, MonitorRequest, MonitorResponse, TrafficInfoEnum,
+use wl_realtime_ogd::{
+    error::WlError,
+    models::{
+        BuildRequestUrl, ExtTrafficInfoEnum, MessageCode, MonitorRequest, MonitorResponse,
+        TrafficInfoEnum,
+    },
 };
 
 #[test]
@@ -13,15 +17,15 @@ fn test_build_request_url_empty() {
 #[test]
 fn test_build_request_url_with_stop_id() {
     let mut request = MonitorRequest::new();
-    request.stop_id.push(123);
+    request.stop_id.push(123.into());
     assert_eq!("/monitor?stopId=123", request.build_request_url());
 }
 
 #[test]
 fn test_build_request_url_with_multiple_stop_ids() {
     let mut request = MonitorRequest::new();
-    request.stop_id.push(123);
-    request.stop_id.push(456);
+    request.stop_id.push(123.into());
+    request.stop_id.push(456.into());
     assert_eq!(
         "/monitor?stopId=123&stopId=456",
         request.build_request_url()
@@ -31,7 +35,7 @@ fn test_build_request_url_with_multiple_stop_ids() {
 #[test]
 fn test_build_request_url_with_diva() {
     let mut request = MonitorRequest::new();
-    request.diva = Some(999);
+    request.diva = Some(999.into());
     assert_eq!("/monitor?diva=999", request.build_request_url());
 }
 
@@ -78,9 +82,9 @@ fn test_build_request_url_with_a_area() {
 #[test]
 fn test_build_request_url_with_all_parameters() {
     let mut request = MonitorRequest::new();
-    request.stop_id.push(123);
-    request.stop_id.push(456);
-    request.diva = Some(999);
+    request.stop_id.push(123.into());
+    request.stop_id.push(456.into());
+    request.diva = Some(999.into());
     request
         .activate_traffic_info
         .push(ExtTrafficInfoEnum::TrafficInfo(
@@ -95,16 +99,56 @@ fn test_build_request_url_with_all_parameters() {
     assert_eq!("/monitor?stopId=123&stopId=456&diva=999&activateTrafficInfo=fahrtreppeninfo&activateTrafficInfo=aufzugsinfo&aArea=1", request.build_request_url());
 }
 
-fn get_mock_data() -> String {
-    let mut file = File::open("./tests/assets/monitor-response.json").unwrap();
+fn read_asset(name: &str) -> String {
+    let mut file = File::open(format!("./tests/assets/{}", name)).unwrap();
     let mut buffer: String = String::new();
     file.read_to_string(&mut buffer).unwrap();
     buffer
 }
 
+fn get_mock_data() -> String {
+    read_asset("monitor-response.json")
+}
+
 #[test]
 fn test_parse_monitor_response() {
     let buffer = get_mock_data();
     let response: MonitorResponse = serde_json::from_str(&buffer).unwrap();
     assert_eq!(response.data.monitors.len(), 2);
 }
+
+#[test]
+fn test_into_result_ok() {
+    let buffer = get_mock_data();
+    let response: MonitorResponse = serde_json::from_str(&buffer).unwrap();
+    assert!(response.into_result().is_ok());
+}
+
+#[test]
+fn test_into_result_api_error() {
+    let buffer = get_mock_data().replace("\"messageCode\": 1", "\"messageCode\": 311");
+    let response: MonitorResponse = serde_json::from_str(&buffer).unwrap();
+    match response.into_result() {
+        Err(WlError::Api { code, .. }) => assert_eq!(code, MessageCode::DbOffline),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_error_responses_without_data() {
+    let expected = [
+        (311, MessageCode::DbOffline),
+        (312, MessageCode::StopDoesNotExist),
+        (316, MessageCode::RequestLimitExceeded),
+        (320, MessageCode::GetParamInvalid),
+        (321, MessageCode::GetParamMissing),
+        (322, MessageCode::NoDataFound),
+    ];
+    for (code, message_code) in expected {
+        let buffer = read_asset(&format!("monitor-response-error-{}.json", code));
+        let response: MonitorResponse = serde_json::from_str(&buffer).unwrap();
+        assert_eq!(response.message.message_code, message_code);
+        assert!(response.data.monitors.is_empty());
+        assert!(!response.message.value.is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use common::{monitor_server, read_asset, MockReply};
+use wl_realtime_ogd::{
+    client::WlClient,
+    error::WlError,
+    models::MonitorRequest,
+    rate_limit::{RateLimit, RateLimitMode, RateLimiter},
+};
+
+#[test]
+fn test_unlimited_limiter_always_grants() {
+    let limiter = RateLimiter::new(RateLimit::new());
+    for _ in 0..1000 {
+        assert!(limiter.try_acquire());
+    }
+}
+
+#[test]
+fn test_limiter_allows_burst_up_to_budget() {
+    let limiter = RateLimiter::new(RateLimit::new().per_second(3));
+    assert!(limiter.try_acquire());
+    assert!(limiter.try_acquire());
+    assert!(limiter.try_acquire());
+    assert!(!limiter.try_acquire());
+}
+
+#[test]
+fn test_strictest_budget_wins() {
+    let limiter = RateLimiter::new(RateLimit::new().per_second(10).per_minute(2));
+    assert!(limiter.try_acquire());
+    assert!(limiter.try_acquire());
+    assert!(!limiter.try_acquire());
+}
+
+#[tokio::test]
+async fn test_fail_fast_mode_returns_rate_limited() {
+    let limiter = RateLimiter::new(RateLimit::new().per_minute(1).mode(RateLimitMode::FailFast));
+    limiter.acquire().await.unwrap();
+    match limiter.acquire().await {
+        Err(WlError::RateLimited { retry_after }) => {
+            assert!(retry_after > Duration::from_secs(50));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_wait_mode_waits_for_token() {
+    let limiter = RateLimiter::new(RateLimit::new().per_second(10));
+    let started = Instant::now();
+    for _ in 0..12 {
+        limiter.acquire().await.unwrap();
+    }
+    assert!(started.elapsed() >= Duration::from_millis(150));
+}
+
+#[tokio::test]
+async fn test_limiter_is_shared_between_client_clones() {
+    let server = monitor_server(vec![MockReply::json(read_asset(
+        "monitor-response-empty.json",
+    ))])
+    .await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .rate_limit(RateLimit::new().per_minute(2).mode(RateLimitMode::FailFast))
+        .build()
+        .unwrap();
+    let clone = client.clone();
+    let request = MonitorRequest::new();
+
+    client.monitor(&request).await.unwrap();
+    clone.monitor(&request).await.unwrap();
+    let error = clone.monitor(&request).await.unwrap_err();
+
+    assert!(matches!(error, WlError::RateLimited { .. }));
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_limiter_can_be_shared_between_clients() {
+    let server = monitor_server(vec![MockReply::json(read_asset(
+        "monitor-response-empty.json",
+    ))])
+    .await;
+    let limiter = Arc::new(RateLimiter::new(
+        RateLimit::new().per_minute(1).mode(RateLimitMode::FailFast),
+    ));
+    let first = WlClient::builder()
+        .base_url(server.base_url())
+        .rate_limiter(limiter.clone())
+        .build()
+        .unwrap();
+    let second = WlClient::builder()
+        .base_url(server.base_url())
+        .rate_limiter(limiter)
+        .build()
+        .unwrap();
+
+    first.monitor(&MonitorRequest::new()).await.unwrap();
+    assert!(second.monitor(&MonitorRequest::new()).await.is_err());
+}
@@ -0,0 +1,122 @@
+use wl_realtime_ogd::{
+    error::RequestValidationError,
+    ids::Diva,
+    models::{
+        BuildRequestUrl, ExtTrafficInfoEnum, MonitorRequest, TrafficInfoEnum,
+        TrafficInfoListRequest, MAX_STOP_IDS,
+    },
+};
+
+#[test]
+fn test_monitor_request_builder() {
+    let request = MonitorRequest::builder()
+        .stop_id(123)
+        .stop_ids([456, 789])
+        .activate_traffic_info(ExtTrafficInfoEnum::TrafficInfo(
+            TrafficInfoEnum::AufzugsInfo,
+        ))
+        .a_area(true)
+        .build()
+        .unwrap();
+    assert_eq!(
+        "/monitor?stopId=123&stopId=456&stopId=789&activateTrafficInfo=aufzugsinfo&aArea=1",
+        request.build_request_url()
+    );
+}
+
+#[test]
+fn test_monitor_request_builder_with_diva_only() {
+    let request = MonitorRequest::builder().diva(60201).build().unwrap();
+    assert_eq!(request.diva, Some(Diva(60201)));
+    assert!(request.stop_id.is_empty());
+}
+
+#[test]
+fn test_monitor_request_requires_stop_or_diva() {
+    assert_eq!(
+        MonitorRequest::builder().a_area(true).build(),
+        Err(RequestValidationError::MissingParameter("stopId or diva"))
+    );
+    assert!(MonitorRequest::new().validate().is_err());
+}
+
+#[test]
+fn test_monitor_request_rejects_duplicates() {
+    assert_eq!(
+        MonitorRequest::builder().stop_ids([1, 2, 1]).build(),
+        Err(RequestValidationError::DuplicateValue {
+            parameter: "stopId",
+            value: String::from("1"),
+        })
+    );
+    assert_eq!(
+        MonitorRequest::builder()
+            .stop_id(1)
+            .activate_traffic_info(ExtTrafficInfoEnum::Information)
+            .activate_traffic_info(ExtTrafficInfoEnum::Information)
+            .build(),
+        Err(RequestValidationError::DuplicateValue {
+            parameter: "activateTrafficInfo",
+            value: String::from("information"),
+        })
+    );
+}
+
+#[test]
+fn test_monitor_request_limits_stop_count() {
+    let stops = 0..MAX_STOP_IDS as u32;
+    assert!(MonitorRequest::builder().stop_ids(stops).build().is_ok());
+
+    let error = MonitorRequest::builder()
+        .stop_ids(0..=MAX_STOP_IDS as u32)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        RequestValidationError::TooManyValues {
+            parameter: "stopId",
+            count: MAX_STOP_IDS + 1,
+            max: MAX_STOP_IDS,
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "51 values for parameter `stopId`, at most 50 are allowed"
+    );
+}
+
+#[test]
+fn test_traffic_info_list_request_builder() {
+    let request = TrafficInfoListRequest::builder()
+        .related_line("U1")
+        .related_stop(4431)
+        .name(TrafficInfoEnum::StoerungKurz)
+        .build()
+        .unwrap();
+    assert_eq!(
+        "/trafficInfoList?relatedLine=U1&relatedStop=4431&name=stoerungkurz",
+        request.build_request_url()
+    );
+    assert!(TrafficInfoListRequest::builder().build().is_ok());
+}
+
+#[test]
+fn test_traffic_info_list_request_validation() {
+    assert_eq!(
+        TrafficInfoListRequest::builder()
+            .related_line("U1")
+            .related_line("U1")
+            .build(),
+        Err(RequestValidationError::DuplicateValue {
+            parameter: "relatedLine",
+            value: String::from("U1"),
+        })
+    );
+    assert!(matches!(
+        TrafficInfoListRequest::builder().related_line(" ").build(),
+        Err(RequestValidationError::InvalidValue {
+            parameter: "relatedLine",
+            ..
+        })
+    ));
+}
@@ -0,0 +1,195 @@
+use proptest::prelude::*;
+use url::Url;
+use wl_realtime_ogd::{
+    error::ParseRequestError,
+    ids::{Diva, LineName, Rbl},
+    models::{
+        BuildRequestUrl, ExtTrafficInfoEnum, MonitorRequest, TrafficInfoEnum,
+        TrafficInfoListRequest,
+    },
+};
+
+#[test]
+fn test_parse_traffic_info_names() {
+    assert_eq!(
+        "aufzugsinfo".parse::<TrafficInfoEnum>(),
+        Ok(TrafficInfoEnum::AufzugsInfo)
+    );
+    assert_eq!(
+        "stoerunglang".parse::<ExtTrafficInfoEnum>(),
+        Ok(ExtTrafficInfoEnum::TrafficInfo(
+            TrafficInfoEnum::StoerungLang
+        ))
+    );
+    assert_eq!(
+        "information".parse::<ExtTrafficInfoEnum>(),
+        Ok(ExtTrafficInfoEnum::Information)
+    );
+    assert_eq!(
+        "information".parse::<TrafficInfoEnum>(),
+        Err(ParseRequestError::UnknownTrafficInfo(String::from(
+            "information"
+        )))
+    );
+}
+
+#[test]
+fn test_parse_monitor_request() {
+    let request: MonitorRequest = "/monitor?stopId=123&activateTrafficInfo=aufzugsinfo&aArea=1"
+        .parse()
+        .unwrap();
+    assert_eq!(request.stop_id, vec![Rbl(123)]);
+    assert_eq!(
+        request.activate_traffic_info,
+        vec![ExtTrafficInfoEnum::TrafficInfo(
+            TrafficInfoEnum::AufzugsInfo
+        )]
+    );
+    assert!(request.a_area);
+    assert_eq!(request.diva, None);
+}
+
+#[test]
+fn test_parse_monitor_request_from_url() {
+    let url = Url::parse("https://www.wienerlinien.at/ogd_realtime/monitor?diva=60201").unwrap();
+    let request = MonitorRequest::try_from(&url).unwrap();
+    assert_eq!(request.diva, Some(Diva(60201)));
+    assert_eq!(
+        "https://www.wienerlinien.at/ogd_realtime/monitor?diva=60201"
+            .parse::<MonitorRequest>()
+            .unwrap(),
+        request
+    );
+}
+
+#[test]
+fn test_parse_monitor_request_errors() {
+    assert_eq!(
+        "/trafficInfoList?stopId=1".parse::<MonitorRequest>(),
+        Err(ParseRequestError::UnexpectedPath(String::from(
+            "/trafficInfoList"
+        )))
+    );
+    assert_eq!(
+        "/foomonitor?stopId=1".parse::<MonitorRequest>(),
+        Err(ParseRequestError::UnexpectedPath(String::from(
+            "/foomonitor"
+        )))
+    );
+    assert_eq!(
+        "/monitor?stop=1".parse::<MonitorRequest>(),
+        Err(ParseRequestError::UnknownParameter(String::from("stop")))
+    );
+    assert_eq!(
+        "/monitor?diva=1&diva=2".parse::<MonitorRequest>(),
+        Err(ParseRequestError::DuplicateParameter(String::from("diva")))
+    );
+    assert_eq!(
+        "/monitor?stopId=abc".parse::<MonitorRequest>(),
+        Err(ParseRequestError::InvalidValue {
+            parameter: String::from("stopId"),
+            value: String::from("abc"),
+        })
+    );
+    assert!(matches!(
+        "/monitor?activateTrafficInfo=elevator".parse::<MonitorRequest>(),
+        Err(ParseRequestError::InvalidValue { .. })
+    ));
+    assert!(matches!(
+        "http://[::1".parse::<MonitorRequest>(),
+        Err(ParseRequestError::InvalidUrl(_))
+    ));
+}
+
+#[test]
+fn test_parse_traffic_info_list_request() {
+    let request: TrafficInfoListRequest =
+        "/trafficInfoList?relatedLine=Badner+Bahn&relatedStop=4431&name=stoerungkurz"
+            .parse()
+            .unwrap();
+    assert_eq!(request.related_line, vec![LineName::new("Badner Bahn")]);
+    assert_eq!(request.related_stop, vec![Rbl(4431)]);
+    assert_eq!(request.name, vec![TrafficInfoEnum::StoerungKurz]);
+}
+
+#[test]
+fn test_parse_traffic_info_list_request_errors() {
+    assert_eq!(
+        "/xtrafficInfoList".parse::<TrafficInfoListRequest>(),
+        Err(ParseRequestError::UnexpectedPath(String::from(
+            "/xtrafficInfoList"
+        )))
+    );
+    assert!(
+        "https://www.wienerlinien.at/ogd_realtime/trafficInfoList?name=stoerungkurz"
+            .parse::<TrafficInfoListRequest>()
+            .is_ok()
+    );
+}
+
+fn traffic_info() -> impl Strategy<Value = TrafficInfoEnum> {
+    prop_oneof![
+        Just(TrafficInfoEnum::StoerungLang),
+        Just(TrafficInfoEnum::StoerungKurz),
+        Just(TrafficInfoEnum::AufzugsInfo),
+        Just(TrafficInfoEnum::FahrtreppenInfo),
+    ]
+}
+
+fn ext_traffic_info() -> impl Strategy<Value = ExtTrafficInfoEnum> {
+    prop_oneof![
+        Just(ExtTrafficInfoEnum::Information),
+        traffic_info().prop_map(ExtTrafficInfoEnum::TrafficInfo),
+    ]
+}
+
+fn monitor_request() -> impl Strategy<Value = MonitorRequest> {
+    (
+        prop::collection::vec(any::<u32>().prop_map(Rbl), 0..5),
+        any::<Option<u32>>().prop_map(|diva| diva.map(Diva)),
+        prop::collection::vec(ext_traffic_info(), 0..5),
+        any::<bool>(),
+    )
+        .prop_map(|(stop_id, diva, activate_traffic_info, a_area)| {
+            let mut request = MonitorRequest::new();
+            request.stop_id = stop_id;
+            request.diva = diva;
+            request.activate_traffic_info = activate_traffic_info;
+            request.a_area = a_area;
+            request
+        })
+}
+
+fn traffic_info_list_request() -> impl Strategy<Value = TrafficInfoListRequest> {
+    (
+        prop::collection::vec(any::<String>().prop_map(LineName::from), 0..5),
+        prop::collection::vec(any::<u32>().prop_map(Rbl), 0..5),
+        prop::collection::vec(traffic_info(), 0..5),
+    )
+        .prop_map(|(related_line, related_stop, name)| {
+            let mut request = TrafficInfoListRequest::new();
+            request.related_line = related_line;
+            request.related_stop = related_stop;
+            request.name = name;
+            request
+        })
+}
+
+proptest! {
+    #[test]
+    fn test_monitor_request_round_trip(request in monitor_request()) {
+        prop_assert_eq!(request.build_request_url().parse::<MonitorRequest>(), Ok(request.clone()));
+        let url = request.request_url("https://www.wienerlinien.at/ogd_realtime").unwrap();
+        prop_assert_eq!(MonitorRequest::try_from(&url), Ok(request));
+    }
+
+    #[test]
+    fn test_traffic_info_list_request_round_trip(request in traffic_info_list_request()) {
+        prop_assert_eq!(
+            request.build_request_url().parse::<TrafficInfoListRequest>(),
+            Ok(request.clone())
+        );
+        let url = request.request_url("https://www.wienerlinien.at/ogd_realtime").unwrap();
+        prop_assert_eq!(TrafficInfoListRequest::try_from(&url), Ok(request));
+    }
+}
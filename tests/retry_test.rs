@@ -0,0 +1,169 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{client_with, monitor_server, read_asset, MockReply};
+use wl_realtime_ogd::{
+    client::WlClient,
+    error::WlError,
+    models::{MessageCode, MonitorRequest},
+    retry::RetryPolicy,
+};
+
+fn fast_policy() -> RetryPolicy {
+    RetryPolicy::new()
+        .base_delay(Duration::from_millis(1))
+        .jitter(0.0)
+}
+
+#[test]
+fn test_delay_grows_exponentially_up_to_max_delay() {
+    let policy = RetryPolicy::new()
+        .base_delay(Duration::from_millis(100))
+        .max_delay(Duration::from_millis(350))
+        .jitter(0.0);
+    assert_eq!(policy.delay(1, None), Duration::from_millis(100));
+    assert_eq!(policy.delay(2, None), Duration::from_millis(200));
+    assert_eq!(policy.delay(3, None), Duration::from_millis(350));
+    assert_eq!(policy.delay(40, None), Duration::from_millis(350));
+}
+
+#[test]
+fn test_delay_with_jitter_stays_within_bounds() {
+    let policy = RetryPolicy::new()
+        .base_delay(Duration::from_millis(100))
+        .jitter(0.5);
+    for _ in 0..100 {
+        let delay = policy.delay(1, None);
+        assert!(delay >= Duration::from_millis(50) && delay <= Duration::from_millis(100));
+    }
+}
+
+#[test]
+fn test_delay_prefers_retry_after() {
+    let policy = RetryPolicy::new();
+    assert_eq!(
+        policy.delay(1, Some(Duration::from_secs(7))),
+        Duration::from_secs(7)
+    );
+    let policy = policy.respect_retry_after(false).jitter(0.0);
+    assert_eq!(
+        policy.delay(1, Some(Duration::from_secs(7))),
+        Duration::from_millis(500)
+    );
+}
+
+#[test]
+fn test_retry_after_is_capped_at_max_delay() {
+    let policy = RetryPolicy::new().max_delay(Duration::from_secs(30));
+    assert_eq!(
+        policy.delay(1, Some(Duration::from_secs(86400))),
+        Duration::from_secs(30)
+    );
+}
+
+#[test]
+fn test_is_retryable_classification() {
+    let policy = RetryPolicy::new();
+    let status = |status| WlError::HttpStatus {
+        status,
+        body: String::new(),
+    };
+    let api = |code| WlError::Api {
+        code,
+        message: String::new(),
+    };
+    assert!(policy.is_retryable(&status(503)));
+    assert!(!policy.is_retryable(&status(404)));
+    assert!(policy.is_retryable(&api(MessageCode::DbOffline)));
+    assert!(policy.is_retryable(&api(MessageCode::RequestLimitExceeded)));
+    assert!(!policy.is_retryable(&api(MessageCode::StopDoesNotExist)));
+
+    let policy = policy
+        .retryable_statuses([404])
+        .retryable_codes([MessageCode::NoDataFound]);
+    assert!(policy.is_retryable(&status(404)));
+    assert!(!policy.is_retryable(&status(503)));
+    assert!(policy.is_retryable(&api(MessageCode::NoDataFound)));
+}
+
+#[tokio::test]
+async fn test_retries_server_errors_until_success() {
+    let server = monitor_server(vec![
+        MockReply::status(503),
+        MockReply::status(502),
+        MockReply::json(read_asset("monitor-response.json")),
+    ])
+    .await;
+    let client = client_with(&server, |builder| builder.retry_policy(fast_policy()));
+
+    let response = client.monitor(&MonitorRequest::new()).await.unwrap();
+    assert_eq!(response.data.monitors.len(), 2);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_retries_retryable_message_codes() {
+    let server = monitor_server(vec![
+        MockReply::json(read_asset("monitor-response-error-311.json")),
+        MockReply::json(read_asset("monitor-response.json")),
+    ])
+    .await;
+    let client = client_with(&server, |builder| builder.retry_policy(fast_policy()));
+
+    assert!(client.monitor(&MonitorRequest::new()).await.is_ok());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let server = monitor_server(vec![MockReply::status(500)]).await;
+    let client = client_with(&server, |builder| {
+        builder.retry_policy(fast_policy().max_attempts(4))
+    });
+
+    let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    assert!(matches!(error, WlError::HttpStatus { status: 500, .. }));
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn test_does_not_retry_permanent_errors() {
+    let server = monitor_server(vec![MockReply::json(read_asset(
+        "monitor-response-error-312.json",
+    ))])
+    .await;
+    let client = client_with(&server, |builder| builder.retry_policy(fast_policy()));
+
+    assert!(client.monitor(&MonitorRequest::new()).await.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_default_client_does_not_retry() {
+    let server = monitor_server(vec![MockReply::status(503)]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    assert!(client.monitor(&MonitorRequest::new()).await.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_honours_retry_after_header() {
+    let server = monitor_server(vec![
+        MockReply::status(429).header("Retry-After", "1"),
+        MockReply::json(read_asset("monitor-response.json")),
+    ])
+    .await;
+    let client = client_with(&server, |builder| builder.retry_policy(fast_policy()));
+
+    let started = Instant::now();
+    assert!(client.monitor(&MonitorRequest::new()).await.is_ok());
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.requests().len(), 2);
+}
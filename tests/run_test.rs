@@ -0,0 +1,42 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use common::request;
+use wl_realtime_ogd::{
+    client::{set_default_client, WlClient},
+    mock_server::MockWlServer,
+    models::TrafficInfoListRequest,
+};
+
+// The default client is global, so this file holds the only test changing it.
+#[test]
+fn test_run_uses_default_client_on_every_runtime() {
+    let server_runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = server_runtime.block_on(MockWlServer::start()).unwrap();
+    set_default_client(WlClient::builder().base_url(server.base_url()));
+
+    // each runtime gets its own client, built from the configured builder
+    for _ in 0..2 {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let response = runtime.block_on(request([4431]).run()).unwrap();
+        assert_eq!(response.data.monitors.len(), 2);
+        let response = runtime
+            .block_on(TrafficInfoListRequest::new().run())
+            .unwrap();
+        assert!(response.data.traffic_infos.is_some());
+    }
+
+    assert_eq!(
+        server.requests(),
+        vec![
+            "/monitor?stopId=4431",
+            "/trafficInfoList?",
+            "/monitor?stopId=4431",
+            "/trafficInfoList?",
+        ]
+    );
+}
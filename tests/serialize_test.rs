@@ -0,0 +1,110 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use common::read_asset;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use wl_realtime_ogd::models::{Message, MonitorResponse, TrafficInfoListResponse};
+
+/// Serializes a parsed fixture and checks the output matches the fixture itself.
+fn assert_round_trip<T>(asset: &str)
+where
+    T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+{
+    let content = read_asset(asset);
+    let parsed: T = serde_json::from_str(&content).unwrap();
+    let serialized = serde_json::to_string(&parsed).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(&serialized).unwrap(),
+        serde_json::from_str::<Value>(&content).unwrap(),
+        "{}",
+        asset
+    );
+    assert_eq!(serde_json::from_str::<T>(&serialized).unwrap(), parsed);
+}
+
+/// Pretty-prints like the fixtures, which keep the formatting of the API.
+fn to_fixture_format<T: Serialize>(value: &T) -> String {
+    let mut output = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+    value
+        .serialize(&mut serde_json::Serializer::with_formatter(
+            &mut output,
+            formatter,
+        ))
+        .unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn test_monitor_response_is_byte_compatible() {
+    for asset in ["monitor-response.json", "monitor-response-empty.json"] {
+        let content = read_asset(asset);
+        let parsed: MonitorResponse = serde_json::from_str(&content).unwrap();
+        assert_eq!(to_fixture_format(&parsed), content.trim_end(), "{}", asset);
+    }
+}
+
+#[test]
+fn test_traffic_info_list_is_byte_compatible() {
+    let content = read_asset("traffic-info-list.json");
+    let parsed: TrafficInfoListResponse = serde_json::from_str(&content).unwrap();
+    assert_eq!(to_fixture_format(&parsed), content.trim_end());
+}
+
+#[test]
+fn test_monitor_response_round_trip() {
+    assert_round_trip::<MonitorResponse>("monitor-response.json");
+    assert_round_trip::<MonitorResponse>("monitor-response-empty.json");
+}
+
+#[test]
+fn test_traffic_info_list_round_trip() {
+    assert_round_trip::<TrafficInfoListResponse>("traffic-info-list.json");
+}
+
+#[test]
+fn test_error_responses_round_trip() {
+    assert_round_trip::<TrafficInfoListResponse>("traffic-info-list-error-322.json");
+
+    // missing data is serialized as empty data
+    let content = read_asset("traffic-info-list-error-320.json");
+    let parsed: TrafficInfoListResponse = serde_json::from_str(&content).unwrap();
+    assert_eq!(
+        serde_json::to_value(&parsed).unwrap()["data"],
+        serde_json::json!({})
+    );
+    for code in [311, 312, 316, 320, 321, 322] {
+        let content = read_asset(&format!("monitor-response-error-{}.json", code));
+        let parsed: MonitorResponse = serde_json::from_str(&content).unwrap();
+        let serialized = serde_json::to_string(&parsed).unwrap();
+        assert_eq!(
+            serde_json::from_str::<MonitorResponse>(&serialized).unwrap(),
+            parsed
+        );
+    }
+}
+
+#[test]
+fn test_dates_keep_wire_format() {
+    let message: Message = serde_json::from_str(
+        r#"{"value":"OK","messageCode":1,"serverTime":"2023-05-29T03:15:16.000+0200"}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        serde_json::to_string(&message).unwrap(),
+        r#"{"value":"OK","messageCode":1,"serverTime":"2023-05-29T03:15:16.000+0200"}"#
+    );
+}
+
+#[test]
+fn test_station_number_is_serialized_as_string() {
+    let response: MonitorResponse =
+        serde_json::from_str(&read_asset("monitor-response.json")).unwrap();
+    let value = serde_json::to_value(&response).unwrap();
+    assert!(value["data"]["monitors"][0]["locationStop"]["properties"]["name"].is_string());
+    assert!(
+        value["data"]["monitors"][0]["locationStop"]["properties"]["attributes"]["rbl"].is_number()
+    );
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{monitor_server, read_asset, request, MockReply};
+use wl_realtime_ogd::{client::WlClient, error::WlError, models::MonitorRequest};
+
+async fn concurrent_monitor(
+    client: &WlClient,
+    requests: Vec<MonitorRequest>,
+) -> Vec<Result<wl_realtime_ogd::models::MonitorResponse, WlError>> {
+    let mut tasks = vec![];
+    for request in requests {
+        let client = client.clone();
+        tasks.push(tokio::spawn(async move { client.monitor(&request).await }));
+    }
+    let mut results = vec![];
+    for task in tasks {
+        results.push(task.await.unwrap());
+    }
+    results
+}
+
+fn slow(response: MockReply) -> MockReply {
+    response.delay(Duration::from_millis(200))
+}
+
+#[tokio::test]
+async fn test_identical_concurrent_requests_share_one_call() {
+    let server = monitor_server(vec![slow(MockReply::json(read_asset(
+        "monitor-response.json",
+    )))])
+    .await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let results = concurrent_monitor(&client, vec![request([1]); 10]).await;
+
+    assert_eq!(server.requests().len(), 1);
+    assert!(results.iter().all(|result| result.is_ok()));
+    assert_eq!(client.in_flight_requests(), 0);
+}
+
+#[tokio::test]
+async fn test_different_requests_are_not_shared() {
+    let server = monitor_server(vec![slow(MockReply::json(read_asset(
+        "monitor-response.json",
+    )))])
+    .await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let results = concurrent_monitor(&client, vec![request([1]), request([2]), request([1])]).await;
+
+    assert_eq!(server.requests().len(), 2);
+    assert!(results.iter().all(|result| result.is_ok()));
+}
+
+#[tokio::test]
+async fn test_errors_are_shared_with_all_callers() {
+    let server = monitor_server(vec![slow(MockReply::status(503))]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let results = concurrent_monitor(&client, vec![request([1]); 5]).await;
+
+    assert_eq!(server.requests().len(), 1);
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(WlError::HttpStatus { status: 503, .. }))));
+}
+
+#[tokio::test]
+async fn test_sequential_requests_are_sent_again() {
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    client.monitor(&request([1])).await.unwrap();
+    client.monitor(&request([1])).await.unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_deduplication_can_be_disabled() {
+    let server = monitor_server(vec![slow(MockReply::json(read_asset(
+        "monitor-response.json",
+    )))])
+    .await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .deduplicate_requests(false)
+        .build()
+        .unwrap();
+
+    concurrent_monitor(&client, vec![request([1]); 3]).await;
+
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_cancelled_leader_does_not_block_followers() {
+    let server = monitor_server(vec![slow(MockReply::json(read_asset(
+        "monitor-response.json",
+    )))])
+    .await;
+    let client = WlClient::builder()
+        .base_url(server.base_url())
+        .build()
+        .unwrap();
+
+    let leader = {
+        let client = client.clone();
+        tokio::spawn(async move { client.monitor(&request([1])).await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let follower = {
+        let client = client.clone();
+        tokio::spawn(async move { client.monitor(&request([1])).await })
+    };
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    leader.abort();
+
+    assert!(follower.await.unwrap().is_ok());
+    assert_eq!(server.requests().len(), 2);
+}
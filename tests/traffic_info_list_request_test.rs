@@ -1,7 +1,11 @@
 use std::{fs::File, io::Read};
 
-use wl_realtime_ogd::models::{
-    BuildRequestUrl, TrafficInfoEnum, TrafficInfoListRequest, TrafficInfoListResponse,
+use wl_realtime_ogd::{
+    error::WlError,
+    models::{
+        BuildRequestUrl, MessageCode, TrafficInfoEnum, TrafficInfoListRequest,
+        TrafficInfoListResponse,
+    },
 };
 
 #[test]
@@ -13,18 +17,44 @@ fn test_build_request_url_empty() {
 #[test]
 fn test_build_request_url_with_related_line() {
     let mut request = TrafficInfoListRequest::new();
-    request.related_line.push(String::from("A1"));
+    request.related_line.push("A1".into());
     assert_eq!(
         "/trafficInfoList?relatedLine=A1",
         request.build_request_url()
     );
 }
 
+#[test]
+fn test_build_request_url_encodes_related_line() {
+    let mut request = TrafficInfoListRequest::new();
+    request.related_line.push("Badner Bahn".into());
+    request.related_line.push("A&B=Ö".into());
+    assert_eq!(
+        "/trafficInfoList?relatedLine=Badner+Bahn&relatedLine=A%26B%3D%C3%96",
+        request.build_request_url()
+    );
+}
+
+#[test]
+fn test_request_url() {
+    let mut request = TrafficInfoListRequest::new();
+    request.related_line.push("Badner Bahn".into());
+    let url = request
+        .request_url("https://www.wienerlinien.at/ogd_realtime/")
+        .unwrap();
+    assert_eq!(url.path(), "/ogd_realtime/trafficInfoList");
+    assert_eq!(
+        url.query_pairs().collect::<Vec<_>>(),
+        vec![("relatedLine".into(), "Badner Bahn".into())]
+    );
+    assert!(request.request_url("not a url").is_err());
+}
+
 #[test]
 fn test_build_request_url_with_multiple_related_lines() {
     let mut request = TrafficInfoListRequest::new();
-    request.related_line.push(String::from("A1"));
-    request.related_line.push(String::from("A2"));
+    request.related_line.push("A1".into());
+    request.related_line.push("A2".into());
     assert_eq!(
         "/trafficInfoList?relatedLine=A1&relatedLine=A2",
         request.build_request_url()
@@ -34,7 +64,7 @@ fn test_build_request_url_with_multiple_related_lines() {
 #[test]
 fn test_build_request_url_with_related_stop() {
     let mut request = TrafficInfoListRequest::new();
-    request.related_stop.push(1);
+    request.related_stop.push(1.into());
     assert_eq!(
         "/trafficInfoList?relatedStop=1",
         request.build_request_url()
@@ -44,8 +74,8 @@ fn test_build_request_url_with_related_stop() {
 #[test]
 fn test_build_request_url_with_multiple_related_stops() {
     let mut request = TrafficInfoListRequest::new();
-    request.related_stop.push(1);
-    request.related_stop.push(2);
+    request.related_stop.push(1.into());
+    request.related_stop.push(2.into());
     assert_eq!(
         "/trafficInfoList?relatedStop=1&relatedStop=2",
         request.build_request_url()
@@ -76,8 +106,8 @@ fn test_build_request_url_with_multiple_names() {
 #[test]
 fn test_build_request_url_with_related_line_stop_and_name() {
     let mut request = TrafficInfoListRequest::new();
-    request.related_line.push(String::from("A1"));
-    request.related_stop.push(1);
+    request.related_line.push("A1".into());
+    request.related_stop.push(1.into());
     request.name.push(TrafficInfoEnum::StoerungLang);
     assert_eq!(
         "/trafficInfoList?relatedLine=A1&relatedStop=1&name=stoerunglang",
@@ -88,10 +118,10 @@ fn test_build_request_url_with_related_line_stop_and_name() {
 #[test]
 fn test_build_request_url_with_multiple_related_lines_stops_and_names() {
     let mut request = TrafficInfoListRequest::new();
-    request.related_line.push(String::from("A1"));
-    request.related_line.push(String::from("A2"));
-    request.related_stop.push(1);
-    request.related_stop.push(2);
+    request.related_line.push("A1".into());
+    request.related_line.push("A2".into());
+    request.related_stop.push(1.into());
+    request.related_stop.push(2.into());
     request.name.push(TrafficInfoEnum::StoerungLang);
     request.name.push(TrafficInfoEnum::AufzugsInfo);
     assert_eq!(
@@ -100,13 +130,17 @@ fn test_build_request_url_with_multiple_related_lines_stops_and_names() {
     );
 }
 
-fn get_mock_data() -> String {
-    let mut file = File::open("./tests/assets/traffic-info-list.json").unwrap();
+fn read_asset(name: &str) -> String {
+    let mut file = File::open(format!("./tests/assets/{}", name)).unwrap();
     let mut buffer: String = String::new();
     file.read_to_string(&mut buffer).unwrap();
     buffer
 }
 
+fn get_mock_data() -> String {
+    read_asset("traffic-info-list.json")
+}
+
 #[test]
 fn test_parse_traffic_info_list_response() {
     let buffer = get_mock_data();
@@ -115,3 +149,26 @@ fn test_parse_traffic_info_list_response() {
     assert_eq!(response.data.traffic_info_categories.unwrap().len(), 2);
     assert_eq!(response.data.traffic_info_category_groups.unwrap().len(), 1);
 }
+
+#[test]
+fn test_into_result_api_error() {
+    let buffer = get_mock_data().replace("\"messageCode\": 1", "\"messageCode\": 316");
+    let response: TrafficInfoListResponse = serde_json::from_str(&buffer).unwrap();
+    match response.into_result() {
+        Err(WlError::Api { code, .. }) => assert_eq!(code, MessageCode::RequestLimitExceeded),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_error_responses_without_data() {
+    for (code, message_code) in [
+        (320, MessageCode::GetParamInvalid),
+        (322, MessageCode::NoDataFound),
+    ] {
+        let buffer = read_asset(&format!("traffic-info-list-error-{}.json", code));
+        let response: TrafficInfoListResponse = serde_json::from_str(&buffer).unwrap();
+        assert_eq!(response.message.message_code, message_code);
+        assert!(response.data.traffic_infos.is_none());
+    }
+}
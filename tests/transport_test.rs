@@ -0,0 +1,120 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use common::read_asset;
+use wl_realtime_ogd::{
+    client::WlClient,
+    error::WlError,
+    models::{MonitorRequest, TrafficInfoListRequest},
+    transport::{HttpResponse, HttpTransport},
+};
+
+#[derive(Debug, Default)]
+struct FakeTransport {
+    responses: Mutex<VecDeque<Result<HttpResponse, WlError>>>,
+    urls: Mutex<Vec<String>>,
+}
+
+impl FakeTransport {
+    fn new(responses: Vec<Result<HttpResponse, WlError>>) -> Arc<Self> {
+        Arc::new(FakeTransport {
+            responses: Mutex::new(VecDeque::from(responses)),
+            urls: Mutex::new(vec![]),
+        })
+    }
+
+    fn urls(&self) -> Vec<String> {
+        self.urls.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl HttpTransport for FakeTransport {
+    async fn get(&self, url: &str) -> Result<HttpResponse, WlError> {
+        self.urls.lock().unwrap().push(url.to_owned());
+        self.responses.lock().unwrap().pop_front().unwrap()
+    }
+}
+
+fn ok(body: String) -> Result<HttpResponse, WlError> {
+    Ok(HttpResponse {
+        status: 200,
+        headers: vec![],
+        body: Bytes::from(body),
+    })
+}
+
+fn client_for(transport: Arc<FakeTransport>) -> WlClient {
+    WlClient::builder()
+        .base_url("https://example.com/ogd")
+        .transport(transport)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_requests_are_sent_through_transport() {
+    let transport = FakeTransport::new(vec![
+        ok(read_asset("monitor-response.json")),
+        ok(read_asset("traffic-info-list.json")),
+    ]);
+    let client = client_for(transport.clone());
+
+    let mut request = MonitorRequest::new();
+    request.stop_id = vec![4431.into()];
+    let response = client.monitor(&request).await.unwrap();
+    assert_eq!(response.data.monitors.len(), 2);
+    assert!(client
+        .traffic_info_list(&TrafficInfoListRequest::new())
+        .await
+        .is_ok());
+    assert_eq!(
+        transport.urls(),
+        vec![
+            "https://example.com/ogd/monitor?stopId=4431",
+            "https://example.com/ogd/trafficInfoList?",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_transport_status_is_checked() {
+    let transport = FakeTransport::new(vec![Ok(HttpResponse {
+        status: 503,
+        headers: vec![],
+        body: Bytes::from_static(b"unavailable"),
+    })]);
+    let client = client_for(transport);
+
+    let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    assert!(matches!(error, WlError::HttpStatus { status: 503, .. }));
+}
+
+#[tokio::test]
+async fn test_transport_errors_are_returned() {
+    let transport = FakeTransport::new(vec![Err(WlError::transport("proxy refused"))]);
+    let client = client_for(transport);
+
+    let error = client.monitor(&MonitorRequest::new()).await.unwrap_err();
+    assert!(matches!(error, WlError::Transport(_)));
+    assert_eq!(error.to_string(), "transport error: proxy refused");
+}
+
+#[test]
+fn test_header_lookup_ignores_case() {
+    let response = HttpResponse {
+        status: 429,
+        headers: vec![(String::from("Retry-After"), String::from("5"))],
+        body: Bytes::new(),
+    };
+    assert_eq!(response.header("retry-after"), Some("5"));
+    assert_eq!(response.header("x-missing"), None);
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "mock-server")]
+
+mod common;
+
+use std::sync::Arc;
+
+use common::{monitor_server, read_asset, MockReply};
+use wl_realtime_ogd::{
+    client::WlClient,
+    error::WlError,
+    models::MonitorRequest,
+    retry::RetryPolicy,
+    transport::{UnrecordedRequest, VcrMode, VcrTransport},
+};
+
+fn client_for(base_url: &str, transport: VcrTransport) -> WlClient {
+    WlClient::builder()
+        .base_url(base_url)
+        .transport(Arc::new(transport))
+        .build()
+        .unwrap()
+}
+
+fn stop_request(stop: u32) -> MonitorRequest {
+    let mut request = MonitorRequest::new();
+    request.stop_id = vec![stop.into()];
+    request
+}
+
+#[tokio::test]
+async fn test_record_then_replay() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cassettes/monitor.json");
+    let server = monitor_server(vec![
+        MockReply::json(read_asset("monitor-response.json")).header("X-Test", "1")
+    ])
+    .await;
+
+    let recorder = client_for(
+        &server.base_url(),
+        VcrTransport::new(&path, VcrMode::Record).unwrap(),
+    );
+    let recorded = recorder.monitor(&stop_request(4431)).await.unwrap();
+    assert_eq!(server.requests().len(), 1);
+
+    let transport = VcrTransport::new(&path, VcrMode::Strict).unwrap();
+    let cassette = transport.cassette().await;
+    assert_eq!(cassette.interactions.len(), 1);
+    let interaction = &cassette.interactions[0];
+    assert_eq!(
+        interaction.url,
+        format!("{}/monitor?stopId=4431", server.base_url())
+    );
+    assert_eq!(interaction.status, 200);
+    assert!(interaction
+        .headers
+        .contains(&(String::from("x-test"), String::from("1"))));
+
+    let replayer = client_for(&server.base_url(), transport);
+    let replayed = replayer.monitor(&stop_request(4431)).await.unwrap();
+    assert_eq!(recorded, replayed);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_strict_mode_fails_on_unrecorded_request() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("monitor.json");
+    let server = monitor_server(vec![MockReply::json(read_asset("monitor-response.json"))]).await;
+    let recorder = client_for(
+        &server.base_url(),
+        VcrTransport::new(&path, VcrMode::Record).unwrap(),
+    );
+    recorder.monitor(&stop_request(4431)).await.unwrap();
+
+    let replayer = client_for(
+        &server.base_url(),
+        VcrTransport::new(&path, VcrMode::Strict).unwrap(),
+    );
+    let error = replayer.monitor(&stop_request(1)).await.unwrap_err();
+    match &error {
+        WlError::Transport(err) => {
+            let unrecorded = err.downcast_ref::<UnrecordedRequest>().unwrap();
+            assert_eq!(
+                unrecorded.url,
+                format!("{}/monitor?stopId=1", server.base_url())
+            );
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+    assert!(!RetryPolicy::new().is_retryable(&error));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_strict_mode_requires_cassette() {
+    let dir = tempfile::tempdir().unwrap();
+    assert!(VcrTransport::new(dir.path().join("missing.json"), VcrMode::Strict).is_err());
+}
+
+#[tokio::test]
+async fn test_replay_mode_records_new_requests() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("monitor.json");
+    let server = monitor_server(vec![
+        MockReply::status(503),
+        MockReply::json(read_asset("monitor-response.json")),
+    ])
+    .await;
+    let client = client_for(
+        &server.base_url(),
+        VcrTransport::new(&path, VcrMode::Replay).unwrap(),
+    );
+
+    let error = client.monitor(&stop_request(4431)).await.unwrap_err();
+    assert!(matches!(error, WlError::HttpStatus { status: 503, .. }));
+    assert!(client.monitor(&stop_request(1)).await.is_ok());
+    assert_eq!(server.requests().len(), 2);
+
+    // repeated requests replay in recorded order and repeat the last recording
+    let transport = VcrTransport::new(&path, VcrMode::Strict).unwrap();
+    assert_eq!(transport.cassette().await.interactions.len(), 2);
+    let client = client_for(&server.base_url(), transport);
+    assert!(client.monitor(&stop_request(4431)).await.is_err());
+    assert!(client.monitor(&stop_request(4431)).await.is_err());
+    assert!(client.monitor(&stop_request(1)).await.is_ok());
+    assert_eq!(server.requests().len(), 2);
+}